@@ -40,6 +40,120 @@ export const batch = new Hono<{ Bindings: Env }>().post('/', async c => {
       .with({ event: 'set_payment_status', data: P.select() }, () => {
         // noop
       })
+      .with({ event: 'propose_partnership', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'new_partnership', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'limit_breach', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'netting_cycle', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'confirm_net_payment', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'settle_netting_cycle', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'deposit_pvp', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'settle_pvp', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'refund_pvp', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'settlement_queue', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'submit_fixing', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'complete_swap', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'request_lifecycle', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'roll_trade', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'terminate_trade', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'miss_cut_off', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'compliance_hold', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'reject_trade', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'set_trader_permissions', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'set_desk_permissions', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'enforce_permissions', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'bank_creation_failed', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'suspend_bank', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'remove_bank', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'pause', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'unpause', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'propose_owner', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'transfer_ownership', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'propose_operation', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'approve_operation', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'execute_operation', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'rotate_operator_key', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'revoke_operator_key', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'set_matching_statuses', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'set_payment_statuses', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'bank_removal_failed', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'fixing_failed', data: P.select() }, () => {
+        // noop
+      })
+      .with({ event: 'lifecycle_failed', data: P.select() }, () => {
+        // noop
+      })
       .exhaustive();
   }
   return new Response(null, { status: 204 });
@@ -81,14 +81,18 @@ export class Partnerships {
           );
 
           let rejectedReason: string | undefined;
+          // client clocks cannot be trusted, so the timeout is based on the block timestamps,
+          // at which the trades reached the chain
+          const timestampA = trade_a.submitted_at.timestamp_ms;
+          const timestampB = trade_b.submitted_at.timestamp_ms;
+          if (Math.abs(timestampA - timestampB) > MAX_TIMESTAMP_DIFF) {
+            rejectedReason = 'trade matching timeout';
+          }
           for (const key of Object.keys(trade_a.trade_details)) {
-            if (key === 'timestamp') {
-              const timestampA = trade_a.trade_details[key];
-              const timestampB = trade_b.trade_details[key];
-              if (Math.abs(timestampA - timestampB) > MAX_TIMESTAMP_DIFF) {
-                rejectedReason = 'trade matching timeout';
-                break;
-              }
+            if (rejectedReason != null) {
+              break;
+            } else if (key === 'event_timestamp' || key === 'recv_time') {
+              continue;
             } else if (key === 'side') {
               const orderSideMatches = match(trade_a.trade_details.side)
                 .with('Buy', () => trade_b.trade_details[key] === 'Sell')
@@ -19,6 +19,7 @@ export type SendTradeData = {
   partnership_id: string;
   bank_id: string;
   trade: TradeDetails;
+  block_time: BlockTime;
 };
 
 export type ConfirmPaymentData = {
@@ -26,6 +27,7 @@ export type ConfirmPaymentData = {
   bank_id: string;
   trade_id: string;
   confirmation: PaymentConfirmation;
+  block_time: BlockTime;
 };
 
 export type MatchingStatus =
@@ -38,10 +40,14 @@ export type MatchingStatus =
 export type PaymentStatus =
   | { status: 'Pending' | 'Error' }
   | {
-      status: 'Confirmed' | 'Rejected';
+      status: 'Confirmed' | 'Rejected' | 'MissedCutOff';
       message: string;
     };
 
+export type ApprovalStatus =
+  | { status: 'AwaitingApproval'; submitted_by: string }
+  | { status: 'Approved' | 'Rejected'; approver: string };
+
 export type Payments = {
   credit: boolean;
   debit: boolean;
@@ -65,6 +71,7 @@ export type Event =
         partnership_id: string;
         trade_id: string;
         matching_status: MatchingStatus;
+        block_time: BlockTime;
       };
     }
   | {
@@ -77,6 +84,316 @@ export type Event =
         partnership_id: string;
         trade_id: string;
         payment_status: PaymentStatus;
+        block_time: BlockTime;
+      };
+    }
+  | {
+      event: 'propose_partnership';
+      data: {
+        partnership_id: string;
+        bank_id: string;
+        terms: PartnershipTerms;
+      };
+    }
+  | {
+      event: 'new_partnership';
+      data: {
+        partnership_id: string;
+        bank_a: string;
+        bank_b: string;
+        terms: PartnershipTerms;
+      };
+    }
+  | {
+      event: 'limit_breach';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        ccy: string;
+        limit: number;
+        exposure: number;
+        amount: number;
+      };
+    }
+  | {
+      event: 'netting_cycle';
+      data: {
+        cycle_id: number;
+        partnership_id: string | null;
+        value_date: string | null;
+        trades: [string, string][];
+        instructions: SettlementInstruction[];
+      };
+    }
+  | {
+      event: 'confirm_net_payment';
+      data: {
+        cycle_id: number;
+        instruction_id: number;
+        instruction: SettlementInstruction;
+      };
+    }
+  | {
+      event: 'settle_netting_cycle';
+      data: {
+        cycle_id: number;
+        status: NettingCycleStatus;
+      };
+    }
+  | {
+      event: 'deposit_pvp';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        bank_id: string;
+        ccy: string;
+        amount: string;
+      };
+    }
+  | {
+      event: 'settle_pvp';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+      };
+    }
+  | {
+      event: 'refund_pvp';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+      };
+    }
+  | {
+      event: 'settlement_queue';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        bank_id: string;
+        ccy: string;
+        amount: string;
+        priority: number;
+        status: QueueStatus;
+      };
+    }
+  | {
+      event: 'submit_fixing';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        fixing: NdfFixing;
+      };
+    }
+  | {
+      event: 'complete_swap';
+      data: {
+        partnership_id: string;
+        bank_id: string;
+        near_trade_id: string;
+        far_trade_id: string;
+      };
+    }
+  | {
+      event: 'request_lifecycle';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        bank_id: string;
+        action: LifecycleAction;
+      };
+    }
+  | {
+      event: 'roll_trade';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        new_trade_id: string;
+        value_date: string;
+        price: number;
+      };
+    }
+  | {
+      event: 'terminate_trade';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        close_out: PaymentLeg;
+        value_date: string;
+      };
+    }
+  | {
+      event: 'miss_cut_off';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        ccy: string;
+        value_date: string;
+      };
+    }
+  | {
+      event: 'compliance_hold';
+      data: {
+        partnership_id: string;
+        bank_id: string;
+        trade_id: string;
+        reason: string;
+      };
+    }
+  | {
+      event: 'reject_trade';
+      data: {
+        partnership_id: string;
+        bank_id: string;
+        trade_id: string;
+        approver: string;
+      };
+    }
+  | {
+      event: 'set_trader_permissions';
+      data: {
+        bank_id: string;
+        trader_id: string;
+        permissions: TraderPermissions | null;
+      };
+    }
+  | {
+      event: 'set_desk_permissions';
+      data: {
+        bank_id: string;
+        desk: string;
+        permissions: DeskPermissions | null;
+      };
+    }
+  | {
+      event: 'enforce_permissions';
+      data: {
+        bank_id: string;
+        enforced: boolean;
+      };
+    }
+  | {
+      event: 'bank_creation_failed';
+      data: {
+        bank: string;
+        bank_id: string;
+        reason: string;
+      };
+    }
+  | {
+      event: 'suspend_bank';
+      data: {
+        bank_id: string;
+        suspended: boolean;
+      };
+    }
+  | {
+      event: 'remove_bank';
+      data: {
+        bank_id: string;
+        archived_trades: number;
+      };
+    }
+  | {
+      event: 'pause';
+      data: {
+        scope: PauseScope;
+        account_id: string;
+      };
+    }
+  | {
+      event: 'unpause';
+      data: {
+        scope: PauseScope;
+        account_id: string;
+      };
+    }
+  | {
+      event: 'propose_owner';
+      data: {
+        owner: string;
+        new_owner: string;
+      };
+    }
+  | {
+      event: 'transfer_ownership';
+      data: {
+        old_owner: string;
+        new_owner: string;
+      };
+    }
+  | {
+      event: 'propose_operation';
+      data: {
+        proposal_id: number;
+        proposer: string;
+        operation: string;
+        expires_at_ms: number;
+      };
+    }
+  | {
+      event: 'approve_operation';
+      data: {
+        proposal_id: number;
+        admin: string;
+        approvals: number;
+      };
+    }
+  | {
+      event: 'execute_operation';
+      data: {
+        proposal_id: number;
+        operation: string;
+      };
+    }
+  | {
+      event: 'rotate_operator_key';
+      data: {
+        bank_id: string;
+        public_key: string;
+      };
+    }
+  | {
+      event: 'revoke_operator_key';
+      data: {
+        bank_id: string;
+        public_key: string;
+      };
+    }
+  | {
+      event: 'set_matching_statuses';
+      data: {
+        outcomes: StatusUpdateOutcome[];
+        block_time: BlockTime;
+      };
+    }
+  | {
+      event: 'set_payment_statuses';
+      data: {
+        outcomes: StatusUpdateOutcome[];
+        block_time: BlockTime;
+      };
+    }
+  | {
+      event: 'bank_removal_failed';
+      data: {
+        bank_id: string;
+        reason: string;
+      };
+    }
+  | {
+      event: 'fixing_failed';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        reason: string;
+      };
+    }
+  | {
+      event: 'lifecycle_failed';
+      data: {
+        partnership_id: string;
+        trade_id: string;
+        reason: string;
       };
     };
 
@@ -86,6 +403,109 @@ export type Trade = {
   matching_status: MatchingStatus;
   payment_status: PaymentStatus;
   payments: Payments;
+  ndf_fixing: NdfFixing | null;
+  lifecycle: LifecycleAction | null;
+  compliance_hold: string | null;
+  approval_status: ApprovalStatus | null;
+  submitted_at: BlockTime;
+  matched_at: BlockTime | null;
+  paid_at: BlockTime | null;
+};
+
+export type BlockTime = {
+  block_height: number;
+  timestamp_ms: number;
+};
+
+export type TradeState = {
+  matching_status: MatchingStatus;
+  payment_status: PaymentStatus;
+  payments: Payments;
+  approval_status: ApprovalStatus | null;
+  ndf_fixing: NdfFixing | null;
+  lifecycle: LifecycleAction | null;
+};
+
+export type AuditEntry = {
+  old_state: TradeState | null;
+  new_state: TradeState;
+  predecessor_id: string;
+  block_height: number;
+  block_timestamp_ms: number;
+  message: string;
 };
 
 export type PaymentConfirmation = 'Credit' | 'Debit';
+
+export type Product = 'SPOT' | 'NDF' | 'FWD' | 'SWAP';
+
+export type TraderPermissions = {
+  desks: string[];
+  legal_entity_ids: string[];
+  products: Product[];
+};
+
+export type DeskPermissions = {
+  legal_entity_ids: string[];
+  products: Product[];
+};
+
+export type PartnershipTerms = {
+  products: Product[];
+  currencies: string[];
+  settlement_methods: string[];
+  matching_rule_set: string;
+};
+
+export type SettlementInstruction = {
+  payer: string;
+  receiver: string;
+  ccy: string;
+  amount: number;
+  confirmed: boolean;
+};
+
+export type NettingCycleStatus = 'Open' | 'Settled' | 'Error';
+
+export type QueueStatus =
+  | { status: 'Cancelled' }
+  | {
+      status: 'Queued' | 'Settled';
+      reason: string;
+    };
+
+export type PaymentLeg = {
+  payer: string;
+  receiver: string;
+  ccy: string;
+  amount: number;
+};
+
+export type NdfFixing = {
+  rate: number;
+  settlement: PaymentLeg;
+};
+
+export type LifecycleAction =
+  | {
+      action: 'Roll';
+      new_trade_id: string;
+      value_date: string;
+      price: number;
+    }
+  | {
+      action: 'Terminate';
+      close_out: PaymentLeg;
+      value_date: string;
+    };
+
+export type PauseScope =
+  | { scope: 'Global' }
+  | { scope: 'Bank'; bank_id: string }
+  | { scope: 'Partnership'; partnership_id: string };
+
+export type StatusUpdateOutcome = {
+  partnership_id: string;
+  trade_id: string;
+  error: string | null;
+};
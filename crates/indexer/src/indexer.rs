@@ -19,7 +19,7 @@ use reqwest::{
     header::{HeaderMap, AUTHORIZATION},
     Client, Url,
 };
-use rtp_common::{ContractEvent, NewBank, RtpEvent, RtpEventKind};
+use rtp_common::{ContractEvent, NewBank, RemoveBank, RtpEvent, RtpEventKind};
 use serde::Deserialize;
 use std::{env, sync::Arc};
 
@@ -167,16 +167,21 @@ fn extract_events(
                     ") ===".bright_yellow(),
                     &event
                 );
-                if let RtpEvent {
-                    event_kind: RtpEventKind::NewBank(NewBank { bank_id, .. }),
-                    ..
-                } = &event
-                {
-                    bank_ids.push(
-                        format!("{}.{}", bank_id, *FACTORY_ACCOUNT_ID)
+                match &event.event_kind {
+                    RtpEventKind::NewBank(NewBank { bank_id, .. }) => {
+                        bank_ids.push(
+                            format!("{}.{}", bank_id, *FACTORY_ACCOUNT_ID)
+                                .parse()
+                                .unwrap(),
+                        );
+                    }
+                    RtpEventKind::RemoveBank(RemoveBank { bank_id, .. }) => {
+                        let account_id: AccountId = format!("{}.{}", bank_id, *FACTORY_ACCOUNT_ID)
                             .parse()
-                            .unwrap(),
-                    );
+                            .unwrap();
+                        bank_ids.retain(|id| *id != account_id);
+                    }
+                    _ => {}
                 }
                 Some(event)
             } else {
@@ -1,5 +1,10 @@
+use near_sdk::json_types::U128;
 use owo_colors::OwoColorize;
-use rtp_contract_common::{MatchingStatus, PaymentConfirmation, PaymentStatus, TradeDetails};
+use rtp_contract_common::{
+    BlockTime, DeskPermissions, LifecycleAction, MatchingStatus, NdfFixing, NettingCycleStatus,
+    PartnershipTerms, PauseScope, PaymentConfirmation, PaymentLeg, PaymentStatus, QueueStatus,
+    SettlementInstruction, StatusUpdateOutcome, TradeDetails, TraderPermissions,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
@@ -10,12 +15,50 @@ pub enum ContractEvent {
     Rtp(RtpEvent),
 }
 
-pub const KNOWN_EVENT_KINDS: [&str; 5] = [
+pub const KNOWN_EVENT_KINDS: [&str; 41] = [
     "new_bank",
     "send_trade",
     "set_matching_status",
     "confirm_payment",
     "set_payment_status",
+    "propose_partnership",
+    "new_partnership",
+    "limit_breach",
+    "netting_cycle",
+    "confirm_net_payment",
+    "settle_netting_cycle",
+    "deposit_pvp",
+    "settle_pvp",
+    "refund_pvp",
+    "settlement_queue",
+    "submit_fixing",
+    "complete_swap",
+    "request_lifecycle",
+    "roll_trade",
+    "terminate_trade",
+    "miss_cut_off",
+    "compliance_hold",
+    "reject_trade",
+    "set_trader_permissions",
+    "set_desk_permissions",
+    "enforce_permissions",
+    "bank_creation_failed",
+    "suspend_bank",
+    "remove_bank",
+    "pause",
+    "unpause",
+    "propose_owner",
+    "transfer_ownership",
+    "propose_operation",
+    "approve_operation",
+    "execute_operation",
+    "rotate_operator_key",
+    "revoke_operator_key",
+    "set_matching_statuses",
+    "set_payment_statuses",
+    "bank_removal_failed",
+    "fixing_failed",
+    "lifecycle_failed",
 ];
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -34,6 +77,44 @@ pub enum RtpEventKind {
     SetMatchingStatus(SetMatchingStatus),
     ConfirmPayment(ConfirmPayment),
     SetPaymentStatus(SetPaymentStatus),
+    ProposePartnership(ProposePartnership),
+    NewPartnership(NewPartnership),
+    LimitBreach(LimitBreach),
+    NettingCycle(NettingCycle),
+    ConfirmNetPayment(ConfirmNetPayment),
+    SettleNettingCycle(SettleNettingCycle),
+    DepositPvp(DepositPvp),
+    SettlePvp(SettlePvp),
+    RefundPvp(RefundPvp),
+    SettlementQueue(SettlementQueue),
+    SubmitFixing(SubmitFixing),
+    CompleteSwap(CompleteSwap),
+    RequestLifecycle(RequestLifecycle),
+    RollTrade(RollTrade),
+    TerminateTrade(TerminateTrade),
+    MissCutOff(MissCutOff),
+    ComplianceHold(ComplianceHold),
+    RejectTrade(RejectTrade),
+    SetTraderPermissions(SetTraderPermissions),
+    SetDeskPermissions(SetDeskPermissions),
+    EnforcePermissions(EnforcePermissions),
+    BankCreationFailed(BankCreationFailed),
+    SuspendBank(SuspendBank),
+    RemoveBank(RemoveBank),
+    Pause(Pause),
+    Unpause(Unpause),
+    ProposeOwner(ProposeOwner),
+    TransferOwnership(TransferOwnership),
+    ProposeOperation(ProposeOperation),
+    ApproveOperation(ApproveOperation),
+    ExecuteOperation(ExecuteOperation),
+    RotateOperatorKey(RotateOperatorKey),
+    RevokeOperatorKey(RevokeOperatorKey),
+    SetMatchingStatuses(SetMatchingStatuses),
+    SetPaymentStatuses(SetPaymentStatuses),
+    BankRemovalFailed(BankRemovalFailed),
+    FixingFailed(FixingFailed),
+    LifecycleFailed(LifecycleFailed),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -47,6 +128,7 @@ pub struct SendTrade {
     pub partnership_id: String,
     pub bank_id: String,
     pub trade: TradeDetails,
+    pub block_time: BlockTime,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -54,6 +136,7 @@ pub struct SetMatchingStatus {
     pub partnership_id: String,
     pub trade_id: String,
     pub matching_status: MatchingStatus,
+    pub block_time: BlockTime,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -62,6 +145,7 @@ pub struct ConfirmPayment {
     pub bank_id: String,
     pub trade_id: String,
     pub confirmation: PaymentConfirmation,
+    pub block_time: BlockTime,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -69,6 +153,278 @@ pub struct SetPaymentStatus {
     pub partnership_id: String,
     pub trade_id: String,
     pub payment_status: PaymentStatus,
+    pub block_time: BlockTime,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ProposePartnership {
+    pub partnership_id: String,
+    pub bank_id: String,
+    pub terms: PartnershipTerms,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct NewPartnership {
+    pub partnership_id: String,
+    pub bank_a: String,
+    pub bank_b: String,
+    pub terms: PartnershipTerms,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LimitBreach {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub ccy: String,
+    pub limit: f64,
+    pub exposure: f64,
+    pub amount: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct NettingCycle {
+    pub cycle_id: u64,
+    pub partnership_id: Option<String>,
+    pub value_date: Option<String>,
+    pub trades: Vec<(String, String)>,
+    pub instructions: Vec<SettlementInstruction>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ConfirmNetPayment {
+    pub cycle_id: u64,
+    pub instruction_id: u32,
+    pub instruction: SettlementInstruction,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SettleNettingCycle {
+    pub cycle_id: u64,
+    pub status: NettingCycleStatus,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DepositPvp {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub bank_id: String,
+    pub ccy: String,
+    pub amount: U128,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SettlePvp {
+    pub partnership_id: String,
+    pub trade_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RefundPvp {
+    pub partnership_id: String,
+    pub trade_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SettlementQueue {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub bank_id: String,
+    pub ccy: String,
+    pub amount: U128,
+    pub priority: u8,
+    pub status: QueueStatus,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SubmitFixing {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub fixing: NdfFixing,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CompleteSwap {
+    pub partnership_id: String,
+    pub bank_id: String,
+    pub near_trade_id: String,
+    pub far_trade_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RequestLifecycle {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub bank_id: String,
+    pub action: LifecycleAction,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RollTrade {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub new_trade_id: String,
+    pub value_date: String,
+    pub price: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TerminateTrade {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub close_out: PaymentLeg,
+    pub value_date: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MissCutOff {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub ccy: String,
+    pub value_date: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ComplianceHold {
+    pub partnership_id: String,
+    pub bank_id: String,
+    pub trade_id: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RejectTrade {
+    pub partnership_id: String,
+    pub bank_id: String,
+    pub trade_id: String,
+    pub approver: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetTraderPermissions {
+    pub bank_id: String,
+    pub trader_id: String,
+    pub permissions: Option<TraderPermissions>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetDeskPermissions {
+    pub bank_id: String,
+    pub desk: String,
+    pub permissions: Option<DeskPermissions>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnforcePermissions {
+    pub bank_id: String,
+    pub enforced: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BankCreationFailed {
+    pub bank: String,
+    pub bank_id: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SuspendBank {
+    pub bank_id: String,
+    pub suspended: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RemoveBank {
+    pub bank_id: String,
+    pub archived_trades: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Pause {
+    pub scope: PauseScope,
+    pub account_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Unpause {
+    pub scope: PauseScope,
+    pub account_id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ProposeOwner {
+    pub owner: String,
+    pub new_owner: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TransferOwnership {
+    pub old_owner: String,
+    pub new_owner: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ProposeOperation {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub operation: String,
+    pub expires_at_ms: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ApproveOperation {
+    pub proposal_id: u64,
+    pub admin: String,
+    pub approvals: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ExecuteOperation {
+    pub proposal_id: u64,
+    pub operation: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RotateOperatorKey {
+    pub bank_id: String,
+    pub public_key: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RevokeOperatorKey {
+    pub bank_id: String,
+    pub public_key: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetMatchingStatuses {
+    pub outcomes: Vec<StatusUpdateOutcome>,
+    pub block_time: BlockTime,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetPaymentStatuses {
+    pub outcomes: Vec<StatusUpdateOutcome>,
+    pub block_time: BlockTime,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BankRemovalFailed {
+    pub bank_id: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FixingFailed {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LifecycleFailed {
+    pub partnership_id: String,
+    pub trade_id: String,
+    pub reason: String,
 }
 
 impl Display for ContractEvent {
@@ -103,6 +459,163 @@ impl Display for RtpEvent {
             RtpEventKind::ConfirmPayment(_) => {
                 formatter.write_fmt(format_args!("{}: confirm_payment", "event".bright_cyan()))?;
             }
+            RtpEventKind::ProposePartnership(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: propose_partnership",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::NewPartnership(_) => {
+                formatter.write_fmt(format_args!("{}: new_partnership", "event".bright_cyan()))?;
+            }
+            RtpEventKind::LimitBreach(_) => {
+                formatter.write_fmt(format_args!("{}: limit_breach", "event".bright_cyan()))?;
+            }
+            RtpEventKind::NettingCycle(_) => {
+                formatter.write_fmt(format_args!("{}: netting_cycle", "event".bright_cyan()))?;
+            }
+            RtpEventKind::ConfirmNetPayment(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: confirm_net_payment",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::SettleNettingCycle(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: settle_netting_cycle",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::DepositPvp(_) => {
+                formatter.write_fmt(format_args!("{}: deposit_pvp", "event".bright_cyan()))?;
+            }
+            RtpEventKind::SettlePvp(_) => {
+                formatter.write_fmt(format_args!("{}: settle_pvp", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RefundPvp(_) => {
+                formatter.write_fmt(format_args!("{}: refund_pvp", "event".bright_cyan()))?;
+            }
+            RtpEventKind::SettlementQueue(_) => {
+                formatter.write_fmt(format_args!("{}: settlement_queue", "event".bright_cyan()))?;
+            }
+            RtpEventKind::SubmitFixing(_) => {
+                formatter.write_fmt(format_args!("{}: submit_fixing", "event".bright_cyan()))?;
+            }
+            RtpEventKind::CompleteSwap(_) => {
+                formatter.write_fmt(format_args!("{}: complete_swap", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RequestLifecycle(_) => {
+                formatter
+                    .write_fmt(format_args!("{}: request_lifecycle", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RollTrade(_) => {
+                formatter.write_fmt(format_args!("{}: roll_trade", "event".bright_cyan()))?;
+            }
+            RtpEventKind::TerminateTrade(_) => {
+                formatter.write_fmt(format_args!("{}: terminate_trade", "event".bright_cyan()))?;
+            }
+            RtpEventKind::MissCutOff(_) => {
+                formatter.write_fmt(format_args!("{}: miss_cut_off", "event".bright_cyan()))?;
+            }
+            RtpEventKind::ComplianceHold(_) => {
+                formatter.write_fmt(format_args!("{}: compliance_hold", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RejectTrade(_) => {
+                formatter.write_fmt(format_args!("{}: reject_trade", "event".bright_cyan()))?;
+            }
+            RtpEventKind::SetTraderPermissions(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: set_trader_permissions",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::SetDeskPermissions(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: set_desk_permissions",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::EnforcePermissions(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: enforce_permissions",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::BankCreationFailed(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: bank_creation_failed",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::SuspendBank(_) => {
+                formatter.write_fmt(format_args!("{}: suspend_bank", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RemoveBank(_) => {
+                formatter.write_fmt(format_args!("{}: remove_bank", "event".bright_cyan()))?;
+            }
+            RtpEventKind::Pause(_) => {
+                formatter.write_fmt(format_args!("{}: pause", "event".bright_cyan()))?;
+            }
+            RtpEventKind::Unpause(_) => {
+                formatter.write_fmt(format_args!("{}: unpause", "event".bright_cyan()))?;
+            }
+            RtpEventKind::ProposeOwner(_) => {
+                formatter.write_fmt(format_args!("{}: propose_owner", "event".bright_cyan()))?;
+            }
+            RtpEventKind::TransferOwnership(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: transfer_ownership",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::ProposeOperation(_) => {
+                formatter
+                    .write_fmt(format_args!("{}: propose_operation", "event".bright_cyan()))?;
+            }
+            RtpEventKind::ApproveOperation(_) => {
+                formatter
+                    .write_fmt(format_args!("{}: approve_operation", "event".bright_cyan()))?;
+            }
+            RtpEventKind::ExecuteOperation(_) => {
+                formatter
+                    .write_fmt(format_args!("{}: execute_operation", "event".bright_cyan()))?;
+            }
+            RtpEventKind::RotateOperatorKey(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: rotate_operator_key",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::RevokeOperatorKey(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: revoke_operator_key",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::SetMatchingStatuses(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: set_matching_statuses",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::SetPaymentStatuses(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: set_payment_statuses",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::BankRemovalFailed(_) => {
+                formatter.write_fmt(format_args!(
+                    "{}: bank_removal_failed",
+                    "event".bright_cyan()
+                ))?;
+            }
+            RtpEventKind::FixingFailed(_) => {
+                formatter.write_fmt(format_args!("{}: fixing_failed", "event".bright_cyan()))?;
+            }
+            RtpEventKind::LifecycleFailed(_) => {
+                formatter.write_fmt(format_args!("{}: lifecycle_failed", "event".bright_cyan()))?;
+            }
         }
         formatter.write_fmt(format_args!("\n{}: rtp", "standard".bright_cyan(),))?;
         formatter.write_fmt(format_args!(
@@ -126,6 +639,120 @@ impl Display for RtpEvent {
             RtpEventKind::ConfirmPayment(data) => {
                 formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
             }
+            RtpEventKind::ProposePartnership(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::NewPartnership(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::LimitBreach(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::NettingCycle(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ConfirmNetPayment(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SettleNettingCycle(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::DepositPvp(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SettlePvp(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RefundPvp(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SettlementQueue(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SubmitFixing(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::CompleteSwap(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RequestLifecycle(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RollTrade(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::TerminateTrade(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::MissCutOff(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ComplianceHold(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RejectTrade(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SetTraderPermissions(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SetDeskPermissions(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::EnforcePermissions(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::BankCreationFailed(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SuspendBank(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RemoveBank(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::Pause(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::Unpause(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ProposeOwner(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::TransferOwnership(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ProposeOperation(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ApproveOperation(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::ExecuteOperation(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RotateOperatorKey(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::RevokeOperatorKey(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SetMatchingStatuses(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::SetPaymentStatuses(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::BankRemovalFailed(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::FixingFailed(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
+            RtpEventKind::LifecycleFailed(data) => {
+                formatter.write_fmt(format_args!("\n{}: {:#?}", "data".bright_cyan(), data))?;
+            }
         }
         Ok(())
     }
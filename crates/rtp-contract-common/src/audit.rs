@@ -0,0 +1,46 @@
+use crate::{
+    ApprovalStatus, LifecycleAction, MatchingStatus, NdfFixing, PaymentStatus, Payments, Trade,
+};
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+/// Mutable state of a trade, which is recorded in its audit trail.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TradeState {
+    pub matching_status: MatchingStatus,
+    pub payment_status: PaymentStatus,
+    pub payments: Payments,
+    pub approval_status: Option<ApprovalStatus>,
+    pub ndf_fixing: Option<NdfFixing>,
+    pub lifecycle: Option<LifecycleAction>,
+}
+
+impl From<&Trade> for TradeState {
+    fn from(trade: &Trade) -> Self {
+        Self {
+            matching_status: trade.matching_status.clone(),
+            payment_status: trade.payment_status.clone(),
+            payments: trade.payments.clone(),
+            approval_status: trade.approval_status.clone(),
+            ndf_fixing: trade.ndf_fixing.clone(),
+            lifecycle: trade.lifecycle.clone(),
+        }
+    }
+}
+
+/// Transition of a trade in its append-only audit trail.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditEntry {
+    /// Not set, if the trade has been created by this transition.
+    pub old_state: Option<TradeState>,
+    pub new_state: TradeState,
+    pub predecessor_id: AccountId,
+    pub block_height: u64,
+    pub block_timestamp_ms: u64,
+    pub message: String,
+}
@@ -0,0 +1,253 @@
+use crate::Settlement;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+/// Calendar date in the `DD.MM.YYYY` format used by trade details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Days since 01.01.1970.
+    pub fn days(&self) -> i64 {
+        // https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Date, which is given number of days after 01.01.1970.
+    pub fn from_days(days: i64) -> Self {
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        Self {
+            year: (year_of_era + era * 400 + (month <= 2) as i64) as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// UTC date at given timestamp in milliseconds.
+    pub fn from_timestamp_ms(timestamp_ms: u64) -> Self {
+        Self::from_days((timestamp_ms / 86_400_000) as i64)
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days(self.days() + days)
+    }
+
+    pub fn is_weekend(&self) -> bool {
+        // 01.01.1970 was a Thursday
+        let weekday = (self.days() + 3).rem_euclid(7);
+        weekday >= 5
+    }
+
+    fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = ();
+
+    fn from_str(date: &str) -> Result<Self, Self::Err> {
+        let mut parts = date.split('.');
+        let (Some(day), Some(month), Some(year), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(());
+        };
+        let date = Self {
+            year: year.parse().map_err(|_| ())?,
+            month: month.parse().map_err(|_| ())?,
+            day: day.parse().map_err(|_| ())?,
+        };
+        if !(1..=12).contains(&date.month)
+            || date.day == 0
+            || date.day > Self::days_in_month(date.year, date.month)
+        {
+            return Err(());
+        }
+        Ok(date)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}.{:02}.{:04}", self.day, self.month, self.year)
+    }
+}
+
+/// Combined calendar of several financial centres, e.g. `NYLN` for New York and London.
+/// A business day is neither a weekend nor a holiday in any of the financial centres.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BusinessCalendar {
+    pub holidays: BTreeSet<Date>,
+}
+
+impl BusinessCalendar {
+    /// Splits a payment calendar like `NYLN` into its two-letter financial centre codes.
+    pub fn financial_centres(payment_calendar: &str) -> Vec<String> {
+        payment_calendar
+            .as_bytes()
+            .chunks(2)
+            .map(|centre| String::from_utf8_lossy(centre).to_uppercase())
+            .collect()
+    }
+
+    pub fn is_business_day(&self, date: &Date) -> bool {
+        !date.is_weekend() && !self.holidays.contains(date)
+    }
+
+    /// First business day on or after given date.
+    pub fn roll_forward(&self, date: &Date) -> Date {
+        let mut date = *date;
+        while !self.is_business_day(&date) {
+            date = date.add_days(1);
+        }
+        date
+    }
+
+    /// Value date of a trade executed at `trade_date`.
+    /// Real-time settlements are due on the trade date or the next business day,
+    /// `T(n)` settlements `n` business days after the trade date.
+    pub fn value_date(&self, trade_date: &Date, settlement: &Settlement) -> Date {
+        match settlement {
+            Settlement::RealTime => self.roll_forward(trade_date),
+            Settlement::T(days) => {
+                let mut date = *trade_date;
+                for _ in 0..*days {
+                    date = self.roll_forward(&date.add_days(1));
+                }
+                date
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> Date {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn test_days_round_trip() {
+        assert_eq!(date("01.01.1970").days(), 0);
+        assert_eq!(date("31.12.1969").days(), -1);
+        for days in [-800_000, -1, 0, 59, 60, 19_700, 800_000] {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+        assert_eq!(Date::from_days(19_699), date("08.12.2023"));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(date("29.02.2024").to_string(), "29.02.2024");
+        assert_eq!(date("1.2.2024").to_string(), "01.02.2024");
+        for invalid in [
+            "29.02.2023",
+            "31.04.2024",
+            "00.01.2024",
+            "01.13.2024",
+            "01.01",
+            "a.b.c",
+        ] {
+            assert!(invalid.parse::<Date>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_add_days() {
+        assert_eq!(date("28.02.2024").add_days(1), date("29.02.2024"));
+        assert_eq!(date("31.12.2023").add_days(1), date("01.01.2024"));
+        assert_eq!(date("01.03.2023").add_days(-1), date("28.02.2023"));
+    }
+
+    #[test]
+    fn test_from_timestamp_ms() {
+        assert_eq!(Date::from_timestamp_ms(0), date("01.01.1970"));
+        assert_eq!(
+            Date::from_timestamp_ms(1_702_079_999_999),
+            date("08.12.2023")
+        );
+        assert_eq!(
+            Date::from_timestamp_ms(1_702_080_000_000),
+            date("09.12.2023")
+        );
+    }
+
+    #[test]
+    fn test_is_weekend() {
+        assert!(!date("08.12.2023").is_weekend());
+        assert!(date("09.12.2023").is_weekend());
+        assert!(date("10.12.2023").is_weekend());
+        assert!(!date("11.12.2023").is_weekend());
+    }
+
+    #[test]
+    fn test_financial_centres() {
+        assert_eq!(
+            BusinessCalendar::financial_centres("nyLN"),
+            vec!["NY".to_string(), "LN".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_roll_forward() {
+        let calendar = BusinessCalendar {
+            holidays: BTreeSet::from([date("11.12.2023")]),
+        };
+        assert!(!calendar.is_business_day(&date("11.12.2023")));
+        assert_eq!(
+            calendar.roll_forward(&date("08.12.2023")),
+            date("08.12.2023")
+        );
+        assert_eq!(
+            calendar.roll_forward(&date("09.12.2023")),
+            date("12.12.2023")
+        );
+    }
+
+    #[test]
+    fn test_value_date() {
+        let calendar = BusinessCalendar {
+            holidays: BTreeSet::from([date("11.12.2023")]),
+        };
+        assert_eq!(
+            calendar.value_date(&date("09.12.2023"), &Settlement::RealTime),
+            date("12.12.2023")
+        );
+        assert_eq!(
+            calendar.value_date(&date("07.12.2023"), &Settlement::T(2)),
+            date("12.12.2023")
+        );
+        assert_eq!(
+            calendar.value_date(&date("08.12.2023"), &Settlement::T(0)),
+            date("08.12.2023")
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::TradeDetails;
+
+/// Reason, why a trade has to be held back from matching, if any.
+/// `screened` is set, if the counterparty is on the screening list.
+pub fn compliance_hold(trade_details: &TradeDetails, screened: bool) -> Option<String> {
+    if !trade_details.aml_check {
+        Some("AML check failed".to_string())
+    } else if !trade_details.sanctions {
+        Some("Sanctions check failed".to_string())
+    } else if !trade_details.authorization {
+        Some("Trade not authorized".to_string())
+    } else if screened {
+        Some("Counterparty is on the screening list".to_string())
+    } else {
+        None
+    }
+}
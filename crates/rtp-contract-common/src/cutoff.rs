@@ -0,0 +1,118 @@
+use crate::Date;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Daily operating window of a currency, in which payments can be made.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentWindow {
+    /// Name of the time zone, e.g. "Europe/Frankfurt".
+    pub time_zone: String,
+    /// Offset of the time zone to UTC in minutes.
+    pub utc_offset_minutes: i16,
+    /// Opening time in minutes after midnight in the time zone.
+    pub opens_at: u16,
+    /// Cut-off time in minutes after midnight in the time zone.
+    pub cut_off: u16,
+}
+
+impl PaymentWindow {
+    pub fn is_valid(&self) -> bool {
+        (-12 * 60..=14 * 60).contains(&self.utc_offset_minutes)
+            && self.opens_at < self.cut_off
+            && self.cut_off as i64 <= MINUTES_PER_DAY
+    }
+
+    /// Date and minutes after midnight in the time zone at given UTC timestamp in milliseconds.
+    pub fn local_time(&self, timestamp_ms: u64) -> (Date, u16) {
+        let minutes = (timestamp_ms / 60_000) as i64 + self.utc_offset_minutes as i64;
+        (
+            Date::from_days(minutes.div_euclid(MINUTES_PER_DAY)),
+            minutes.rem_euclid(MINUTES_PER_DAY) as u16,
+        )
+    }
+
+    pub fn is_open(&self, timestamp_ms: u64) -> bool {
+        let (_, minutes) = self.local_time(timestamp_ms);
+        (self.opens_at..self.cut_off).contains(&minutes)
+    }
+
+    /// Whether the cut-off for a payment due at `value_date` has already passed.
+    pub fn is_missed(&self, value_date: &Date, timestamp_ms: u64) -> bool {
+        let (date, minutes) = self.local_time(timestamp_ms);
+        date > *value_date || (date == *value_date && minutes >= self.cut_off)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 09.12.2023 00:00 UTC
+    const MIDNIGHT_MS: u64 = 1_702_080_000_000;
+    const MINUTE_MS: u64 = 60_000;
+
+    fn window() -> PaymentWindow {
+        PaymentWindow {
+            time_zone: "Europe/Frankfurt".to_string(),
+            utc_offset_minutes: 60,
+            opens_at: 7 * 60,
+            cut_off: 16 * 60,
+        }
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(window().is_valid());
+        assert!(!PaymentWindow {
+            opens_at: 16 * 60,
+            cut_off: 7 * 60,
+            ..window()
+        }
+        .is_valid());
+        assert!(!PaymentWindow {
+            cut_off: 24 * 60 + 1,
+            ..window()
+        }
+        .is_valid());
+        assert!(!PaymentWindow {
+            utc_offset_minutes: 15 * 60,
+            ..window()
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_local_time() {
+        let date: Date = "09.12.2023".parse().unwrap();
+        assert_eq!(window().local_time(MIDNIGHT_MS), (date, 60));
+        let window = PaymentWindow {
+            utc_offset_minutes: -5 * 60,
+            ..window()
+        };
+        assert_eq!(window.local_time(MIDNIGHT_MS), (date.add_days(-1), 19 * 60));
+    }
+
+    #[test]
+    fn test_is_open() {
+        let window = window();
+        assert!(!window.is_open(MIDNIGHT_MS + 5 * 60 * MINUTE_MS));
+        assert!(window.is_open(MIDNIGHT_MS + 6 * 60 * MINUTE_MS));
+        assert!(window.is_open(MIDNIGHT_MS + (15 * 60 - 1) * MINUTE_MS));
+        assert!(!window.is_open(MIDNIGHT_MS + 15 * 60 * MINUTE_MS));
+    }
+
+    #[test]
+    fn test_is_missed() {
+        let window = window();
+        let value_date: Date = "09.12.2023".parse().unwrap();
+        assert!(!window.is_missed(&value_date, MIDNIGHT_MS + (15 * 60 - 1) * MINUTE_MS));
+        assert!(window.is_missed(&value_date, MIDNIGHT_MS + 15 * 60 * MINUTE_MS));
+        assert!(!window.is_missed(&value_date.add_days(1), MIDNIGHT_MS + 15 * 60 * MINUTE_MS));
+        assert!(window.is_missed(&value_date.add_days(-1), MIDNIGHT_MS));
+    }
+}
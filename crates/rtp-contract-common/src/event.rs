@@ -1,5 +1,9 @@
-use crate::{MatchingStatus, PaymentConfirmation, PaymentStatus, TradeDetails};
-use near_sdk::near_bindgen;
+use crate::{
+    BlockTime, DeskPermissions, LifecycleAction, MatchingStatus, NdfFixing, NettingCycleStatus,
+    PartnershipTerms, PauseScope, PaymentConfirmation, PaymentLeg, PaymentStatus, QueueStatus,
+    SettlementInstruction, StatusUpdateOutcome, TradeDetails, TraderPermissions,
+};
+use near_sdk::{json_types::U128, near_bindgen};
 
 #[near_bindgen(event_json(standard = "rtp"))]
 #[derive(Debug)]
@@ -11,12 +15,14 @@ pub enum RtpEvent {
         partnership_id: String,
         bank_id: String,
         trade: TradeDetails,
+        block_time: BlockTime,
     },
     #[event_version("1.0.0")]
     SetMatchingStatus {
         partnership_id: String,
         trade_id: String,
         matching_status: MatchingStatus,
+        block_time: BlockTime,
     },
     #[event_version("1.0.0")]
     ConfirmPayment {
@@ -24,11 +30,226 @@ pub enum RtpEvent {
         bank_id: String,
         trade_id: String,
         confirmation: PaymentConfirmation,
+        block_time: BlockTime,
     },
     #[event_version("1.0.0")]
     SetPaymentStatus {
         partnership_id: String,
         trade_id: String,
         payment_status: PaymentStatus,
+        block_time: BlockTime,
+    },
+    #[event_version("1.0.0")]
+    ProposePartnership {
+        partnership_id: String,
+        bank_id: String,
+        terms: PartnershipTerms,
+    },
+    #[event_version("1.0.0")]
+    NewPartnership {
+        partnership_id: String,
+        bank_a: String,
+        bank_b: String,
+        terms: PartnershipTerms,
+    },
+    #[event_version("1.0.0")]
+    LimitBreach {
+        partnership_id: String,
+        trade_id: String,
+        ccy: String,
+        limit: f64,
+        exposure: f64,
+        amount: f64,
+    },
+    #[event_version("1.0.0")]
+    NettingCycle {
+        cycle_id: u64,
+        partnership_id: Option<String>,
+        value_date: Option<String>,
+        trades: Vec<(String, String)>,
+        instructions: Vec<SettlementInstruction>,
+    },
+    #[event_version("1.0.0")]
+    ConfirmNetPayment {
+        cycle_id: u64,
+        instruction_id: u32,
+        instruction: SettlementInstruction,
+    },
+    #[event_version("1.0.0")]
+    SettleNettingCycle {
+        cycle_id: u64,
+        status: NettingCycleStatus,
+    },
+    #[event_version("1.0.0")]
+    DepositPvp {
+        partnership_id: String,
+        trade_id: String,
+        bank_id: String,
+        ccy: String,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    SettlePvp {
+        partnership_id: String,
+        trade_id: String,
+    },
+    #[event_version("1.0.0")]
+    RefundPvp {
+        partnership_id: String,
+        trade_id: String,
+    },
+    #[event_version("1.0.0")]
+    SettlementQueue {
+        partnership_id: String,
+        trade_id: String,
+        /// Bank ID of the paying bank, whose queue changed.
+        bank_id: String,
+        ccy: String,
+        amount: U128,
+        priority: u8,
+        status: QueueStatus,
+    },
+    #[event_version("1.0.0")]
+    SubmitFixing {
+        partnership_id: String,
+        trade_id: String,
+        fixing: NdfFixing,
+    },
+    #[event_version("1.0.0")]
+    CompleteSwap {
+        partnership_id: String,
+        bank_id: String,
+        near_trade_id: String,
+        far_trade_id: String,
+    },
+    #[event_version("1.0.0")]
+    RequestLifecycle {
+        partnership_id: String,
+        trade_id: String,
+        bank_id: String,
+        action: LifecycleAction,
+    },
+    #[event_version("1.0.0")]
+    RollTrade {
+        partnership_id: String,
+        trade_id: String,
+        new_trade_id: String,
+        value_date: String,
+        price: f32,
+    },
+    #[event_version("1.0.0")]
+    TerminateTrade {
+        partnership_id: String,
+        trade_id: String,
+        close_out: PaymentLeg,
+        value_date: String,
+    },
+    #[event_version("1.0.0")]
+    MissCutOff {
+        partnership_id: String,
+        trade_id: String,
+        ccy: String,
+        value_date: String,
+    },
+    #[event_version("1.0.0")]
+    ComplianceHold {
+        partnership_id: String,
+        bank_id: String,
+        trade_id: String,
+        reason: String,
+    },
+    #[event_version("1.0.0")]
+    RejectTrade {
+        partnership_id: String,
+        bank_id: String,
+        trade_id: String,
+        approver: String,
+    },
+    #[event_version("1.0.0")]
+    SetTraderPermissions {
+        bank_id: String,
+        trader_id: String,
+        permissions: Option<TraderPermissions>,
+    },
+    #[event_version("1.0.0")]
+    SetDeskPermissions {
+        bank_id: String,
+        desk: String,
+        permissions: Option<DeskPermissions>,
+    },
+    #[event_version("1.0.0")]
+    EnforcePermissions { bank_id: String, enforced: bool },
+    #[event_version("1.0.0")]
+    BankCreationFailed {
+        bank: String,
+        bank_id: String,
+        reason: String,
+    },
+    #[event_version("1.0.0")]
+    SuspendBank { bank_id: String, suspended: bool },
+    #[event_version("1.0.0")]
+    RemoveBank {
+        bank_id: String,
+        archived_trades: u64,
+    },
+    #[event_version("1.0.0")]
+    BankRemovalFailed { bank_id: String, reason: String },
+    #[event_version("1.0.0")]
+    Pause {
+        scope: PauseScope,
+        account_id: String,
+    },
+    #[event_version("1.0.0")]
+    Unpause {
+        scope: PauseScope,
+        account_id: String,
+    },
+    #[event_version("1.0.0")]
+    ProposeOwner { owner: String, new_owner: String },
+    #[event_version("1.0.0")]
+    TransferOwnership {
+        old_owner: String,
+        new_owner: String,
+    },
+    #[event_version("1.0.0")]
+    ProposeOperation {
+        proposal_id: u64,
+        proposer: String,
+        operation: String,
+        expires_at_ms: u64,
+    },
+    #[event_version("1.0.0")]
+    ApproveOperation {
+        proposal_id: u64,
+        admin: String,
+        approvals: u32,
+    },
+    #[event_version("1.0.0")]
+    ExecuteOperation { proposal_id: u64, operation: String },
+    #[event_version("1.0.0")]
+    RotateOperatorKey { bank_id: String, public_key: String },
+    #[event_version("1.0.0")]
+    RevokeOperatorKey { bank_id: String, public_key: String },
+    #[event_version("1.0.0")]
+    SetMatchingStatuses {
+        outcomes: Vec<StatusUpdateOutcome>,
+        block_time: BlockTime,
+    },
+    #[event_version("1.0.0")]
+    SetPaymentStatuses {
+        outcomes: Vec<StatusUpdateOutcome>,
+        block_time: BlockTime,
+    },
+    #[event_version("1.0.0")]
+    FixingFailed {
+        partnership_id: String,
+        trade_id: String,
+        reason: String,
+    },
+    #[event_version("1.0.0")]
+    LifecycleFailed {
+        partnership_id: String,
+        trade_id: String,
+        reason: String,
     },
 }
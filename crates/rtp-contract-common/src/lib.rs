@@ -1,7 +1,37 @@
+mod audit;
+mod calendar;
+mod compliance;
+mod cutoff;
 mod event;
+mod lifecycle;
+mod multisig;
+mod ndf;
+mod netting;
+mod operator;
+mod partnership;
+mod pause;
+mod permission;
+mod pvp;
+mod queue;
+mod role;
 mod trade;
 
+pub use audit::*;
+pub use calendar::*;
+pub use compliance::*;
+pub use cutoff::*;
 pub use event::*;
+pub use lifecycle::*;
+pub use multisig::*;
+pub use ndf::*;
+pub use netting::*;
+pub use operator::*;
+pub use partnership::*;
+pub use pause::*;
+pub use permission::*;
+pub use pvp::*;
+pub use queue::*;
+pub use role::*;
 pub use trade::*;
 
 use std::{
@@ -0,0 +1,37 @@
+use crate::PaymentLeg;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Lifecycle operation on a matched forward, which both counterparties have to request.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+pub enum LifecycleAction {
+    /// Close the forward and replace it by a new trade with a later value date and a new price.
+    Roll {
+        new_trade_id: String,
+        value_date: String,
+        price: f32,
+    },
+    /// Close the forward early against a close-out amount, which is paid at `value_date`.
+    Terminate {
+        close_out: PaymentLeg,
+        value_date: String,
+    },
+}
+
+/// Lifecycle operation requested by one counterparty, which waits for the other counterparty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LifecycleRequest {
+    /// Bank ID of the requesting bank.
+    pub bank_id: String,
+    pub action: LifecycleAction,
+}
+
+/// ID of the trade, which pays the close-out amount of a terminated trade.
+pub fn close_out_trade_id(trade_id: &str) -> String {
+    format!("{trade_id}-close-out")
+}
@@ -0,0 +1,137 @@
+use crate::{CurrencyToken, PaymentWindow, Role};
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::{Base64VecU8, U128},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+/// Set of admins, of which `threshold` have to approve every critical operation of the factory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MultisigConfig {
+    pub admins: Vec<AccountId>,
+    pub threshold: u32,
+    /// Duration in milliseconds, after which an unexecuted proposal expires.
+    pub proposal_ttl_ms: u64,
+}
+
+impl MultisigConfig {
+    pub fn is_valid(&self) -> bool {
+        self.threshold > 0 && self.threshold as usize <= self.admins.len()
+    }
+}
+
+/// Critical operation of the factory, which requires the approval of the admins.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "operation")]
+pub enum AdminOperation {
+    /// Store the binary of the bank contract.
+    StoreContract {
+        code: Base64VecU8,
+    },
+    /// Deploy a new binary of the factory contract.
+    Upgrade {
+        code: Base64VecU8,
+    },
+    RemoveBank {
+        bank_id: String,
+    },
+    GrantRole {
+        role: Role,
+        account_id: AccountId,
+    },
+    RevokeRole {
+        role: Role,
+        account_id: AccountId,
+    },
+    /// Replace the admin set or switch back to the sole approval of the owner, if `None`.
+    SetMultisig {
+        config: Option<MultisigConfig>,
+    },
+    /// Clear the storage of the factory for testing, except for its owner, roles and admins.
+    ClearStorage,
+    SuspendBank {
+        bank_id: String,
+        suspended: bool,
+    },
+    SetHolidays {
+        financial_centre: String,
+        holidays: Vec<String>,
+    },
+    SetPaymentWindow {
+        ccy: String,
+        window: Option<PaymentWindow>,
+    },
+    SetExposureLimit {
+        partnership_id: String,
+        ccy: String,
+        limit: Option<f64>,
+    },
+    SetCurrencyToken {
+        ccy: String,
+        token: Option<CurrencyToken>,
+    },
+    SetSettlementAccount {
+        bank_id: String,
+        account_id: Option<AccountId>,
+    },
+    WithdrawLiquidity {
+        bank_id: String,
+        ccy: String,
+        amount: U128,
+        receiver_id: AccountId,
+    },
+    ProposeOwner {
+        new_owner: AccountId,
+    },
+}
+
+impl AdminOperation {
+    /// Name of the operation without its potentially large arguments.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdminOperation::StoreContract { .. } => "store_contract",
+            AdminOperation::Upgrade { .. } => "upgrade",
+            AdminOperation::RemoveBank { .. } => "remove_bank",
+            AdminOperation::GrantRole { .. } => "grant_role",
+            AdminOperation::RevokeRole { .. } => "revoke_role",
+            AdminOperation::SetMultisig { .. } => "set_multisig",
+            AdminOperation::ClearStorage => "clear_storage",
+            AdminOperation::SuspendBank { .. } => "suspend_bank",
+            AdminOperation::SetHolidays { .. } => "set_holidays",
+            AdminOperation::SetPaymentWindow { .. } => "set_payment_window",
+            AdminOperation::SetExposureLimit { .. } => "set_exposure_limit",
+            AdminOperation::SetCurrencyToken { .. } => "set_currency_token",
+            AdminOperation::SetSettlementAccount { .. } => "set_settlement_account",
+            AdminOperation::WithdrawLiquidity { .. } => "withdraw_liquidity",
+            AdminOperation::ProposeOwner { .. } => "propose_owner",
+        }
+    }
+}
+
+/// Proposed critical operation and the admins, who approved it.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminProposal {
+    pub operation: AdminOperation,
+    pub proposer: AccountId,
+    pub approvals: Vec<AccountId>,
+    pub expires_at_ms: u64,
+    pub executed: bool,
+}
+
+impl AdminProposal {
+    pub fn is_expired(&self, timestamp_ms: u64) -> bool {
+        !self.executed && timestamp_ms >= self.expires_at_ms
+    }
+
+    /// Number of approvals by accounts, which are still admins.
+    pub fn approval_count(&self, config: &MultisigConfig) -> u32 {
+        self.approvals
+            .iter()
+            .filter(|admin| config.admins.contains(admin))
+            .count() as u32
+    }
+}
@@ -0,0 +1,120 @@
+use crate::PaymentLeg;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Attributes of a non-deliverable forward, which is cash-settled at the fixing rate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NdfTerms {
+    pub fixing_date: String,
+    /// Source of the fixing rate, e.g. "WMR" or "PTAX".
+    pub fixing_source: String,
+    /// Either the base or the quote currency of the instrument.
+    pub settlement_ccy: String,
+}
+
+/// Non-deliverable forward between two banks as tracked by the factory.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NdfContract {
+    /// Bank ID of the buyer of the base currency.
+    pub buyer: String,
+    /// Bank ID of the seller of the base currency.
+    pub seller: String,
+    pub base_ccy: String,
+    pub quote_ccy: String,
+    /// Notional amount in base currency.
+    pub notional_amount: f64,
+    pub contract_rate: f64,
+    pub terms: NdfTerms,
+    pub fixing: Option<NdfFixing>,
+}
+
+impl NdfContract {
+    /// Net cash-settlement at given fixing rate.
+    /// The buyer receives the difference to the contract rate, if the fixing rate is higher,
+    /// and pays it otherwise.
+    pub fn settle(&self, fixing_rate: f64) -> PaymentLeg {
+        let difference = self.notional_amount * (fixing_rate - self.contract_rate);
+        let amount = if self.terms.settlement_ccy == self.base_ccy {
+            difference / fixing_rate
+        } else {
+            difference
+        };
+        let (payer, receiver) = if amount >= 0. {
+            (&self.seller, &self.buyer)
+        } else {
+            (&self.buyer, &self.seller)
+        };
+        PaymentLeg {
+            payer: payer.clone(),
+            receiver: receiver.clone(),
+            ccy: self.terms.settlement_ccy.clone(),
+            amount: amount.abs(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NdfFixing {
+    pub rate: f64,
+    /// Single net payment, which settles the NDF.
+    pub settlement: PaymentLeg,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ndf(settlement_ccy: &str) -> NdfContract {
+        NdfContract {
+            buyer: "a".to_string(),
+            seller: "b".to_string(),
+            base_ccy: "USD".to_string(),
+            quote_ccy: "BRL".to_string(),
+            notional_amount: 1_000.,
+            contract_rate: 5.,
+            terms: NdfTerms {
+                fixing_date: "09.12.2023".to_string(),
+                fixing_source: "PTAX".to_string(),
+                settlement_ccy: settlement_ccy.to_string(),
+            },
+            fixing: None,
+        }
+    }
+
+    #[test]
+    fn test_settle_in_base_ccy() {
+        let settlement = ndf("USD").settle(5.5);
+        assert_eq!(settlement.payer, "b");
+        assert_eq!(settlement.receiver, "a");
+        assert_eq!(settlement.ccy, "USD");
+        assert!((settlement.amount - 1_000. * 0.5 / 5.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settle_in_quote_ccy() {
+        let settlement = ndf("BRL").settle(5.5);
+        assert_eq!(settlement.payer, "b");
+        assert_eq!(settlement.receiver, "a");
+        assert_eq!(settlement.ccy, "BRL");
+        assert!((settlement.amount - 500.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settle_below_contract_rate() {
+        let settlement = ndf("BRL").settle(4.5);
+        assert_eq!(settlement.payer, "a");
+        assert_eq!(settlement.receiver, "b");
+        assert!((settlement.amount - 500.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settle_at_contract_rate() {
+        let settlement = ndf("USD").settle(5.);
+        assert_eq!(settlement.amount, 0.);
+    }
+}
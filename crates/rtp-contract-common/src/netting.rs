@@ -0,0 +1,205 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+use std::collections::BTreeMap;
+
+/// Amounts below this threshold are considered to be fully netted.
+const NETTING_EPSILON: f64 = 1e-6;
+
+/// A single payment, which has to be made to settle a trade.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentLeg {
+    /// Bank ID of the paying bank.
+    pub payer: String,
+    /// Bank ID of the receiving bank.
+    pub receiver: String,
+    pub ccy: String,
+    pub amount: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SettlementInstruction {
+    /// Bank ID of the paying bank.
+    pub payer: String,
+    /// Bank ID of the receiving bank.
+    pub receiver: String,
+    pub ccy: String,
+    pub amount: f64,
+    pub confirmed: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NettingCycle {
+    /// Partnership ID of a bilateral netting cycle.
+    pub partnership_id: Option<String>,
+    pub value_date: Option<String>,
+    /// Netted trades by partnership ID and trade ID.
+    pub trades: Vec<(String, String)>,
+    pub instructions: Vec<SettlementInstruction>,
+    pub status: NettingCycleStatus,
+}
+
+impl NettingCycle {
+    pub fn is_confirmed(&self) -> bool {
+        self.instructions
+            .iter()
+            .all(|instruction| instruction.confirmed)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum NettingCycleStatus {
+    Open,
+    Settled,
+    Error,
+}
+
+/// Net position of a bank in a single currency.
+/// Positive amounts need to be received, negative amounts need to be paid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NetPosition {
+    pub bank_id: String,
+    pub ccy: String,
+    pub amount: f64,
+}
+
+/// Aggregates payment legs into the net position of every bank per currency.
+/// Positive amounts need to be received, negative amounts need to be paid.
+pub fn net_positions<'a>(
+    legs: impl IntoIterator<Item = &'a PaymentLeg>,
+) -> BTreeMap<String, BTreeMap<String, f64>> {
+    let mut positions: BTreeMap<String, BTreeMap<String, f64>> = BTreeMap::new();
+    for leg in legs {
+        let ccy_positions = positions.entry(leg.ccy.clone()).or_default();
+        *ccy_positions.entry(leg.payer.clone()).or_default() -= leg.amount;
+        *ccy_positions.entry(leg.receiver.clone()).or_default() += leg.amount;
+    }
+    positions
+}
+
+/// Nets payment legs into the minimal set of settlement instructions per currency.
+pub fn net_legs<'a>(legs: impl IntoIterator<Item = &'a PaymentLeg>) -> Vec<SettlementInstruction> {
+    let mut instructions = vec![];
+    for (ccy, positions) in net_positions(legs) {
+        let mut payers: Vec<_> = positions
+            .iter()
+            .filter(|(_, amount)| **amount < -NETTING_EPSILON)
+            .map(|(bank_id, amount)| (bank_id.clone(), -amount))
+            .collect();
+        let mut receivers: Vec<_> = positions
+            .iter()
+            .filter(|(_, amount)| **amount > NETTING_EPSILON)
+            .map(|(bank_id, amount)| (bank_id.clone(), *amount))
+            .collect();
+
+        let (mut payer_index, mut receiver_index) = (0, 0);
+        while payer_index < payers.len() && receiver_index < receivers.len() {
+            let (payer, payer_amount) = &mut payers[payer_index];
+            let (receiver, receiver_amount) = &mut receivers[receiver_index];
+            let amount = payer_amount.min(*receiver_amount);
+            instructions.push(SettlementInstruction {
+                payer: payer.clone(),
+                receiver: receiver.clone(),
+                ccy: ccy.clone(),
+                amount,
+                confirmed: false,
+            });
+            *payer_amount -= amount;
+            *receiver_amount -= amount;
+            if *payer_amount <= NETTING_EPSILON {
+                payer_index += 1;
+            }
+            if *receiver_amount <= NETTING_EPSILON {
+                receiver_index += 1;
+            }
+        }
+    }
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(payer: &str, receiver: &str, ccy: &str, amount: f64) -> PaymentLeg {
+        PaymentLeg {
+            payer: payer.to_string(),
+            receiver: receiver.to_string(),
+            ccy: ccy.to_string(),
+            amount,
+        }
+    }
+
+    fn instruction(payer: &str, receiver: &str, ccy: &str, amount: f64) -> SettlementInstruction {
+        SettlementInstruction {
+            payer: payer.to_string(),
+            receiver: receiver.to_string(),
+            ccy: ccy.to_string(),
+            amount,
+            confirmed: false,
+        }
+    }
+
+    #[test]
+    fn test_net_legs_bilateral() {
+        let legs = [
+            leg("a", "b", "EUR", 1_000.),
+            leg("b", "a", "USD", 1_100.),
+            leg("b", "a", "EUR", 400.),
+            leg("a", "b", "USD", 410.),
+        ];
+        assert_eq!(
+            net_legs(legs.iter()),
+            vec![
+                instruction("a", "b", "EUR", 600.),
+                instruction("b", "a", "USD", 690.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_legs_fully_offsetting() {
+        let legs = [leg("a", "b", "EUR", 500.), leg("b", "a", "EUR", 500.)];
+        assert!(net_legs(legs.iter()).is_empty());
+    }
+
+    #[test]
+    fn test_net_legs_multilateral() {
+        // a pays b, b pays c and c pays a, so b is flat
+        let legs = [
+            leg("a", "b", "EUR", 1_000.),
+            leg("b", "c", "EUR", 1_000.),
+            leg("c", "a", "EUR", 500.),
+        ];
+        assert_eq!(
+            net_legs(legs.iter()),
+            vec![instruction("a", "c", "EUR", 500.)]
+        );
+    }
+
+    #[test]
+    fn test_net_legs_split_between_receivers() {
+        let legs = [leg("a", "b", "EUR", 300.), leg("a", "c", "EUR", 200.)];
+        assert_eq!(
+            net_legs(legs.iter()),
+            vec![
+                instruction("a", "b", "EUR", 300.),
+                instruction("a", "c", "EUR", 200.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_positions() {
+        let legs = [leg("a", "b", "EUR", 1_000.), leg("b", "a", "EUR", 400.)];
+        let positions = net_positions(legs.iter());
+        assert_eq!(positions["EUR"]["a"], -600.);
+        assert_eq!(positions["EUR"]["b"], 600.);
+    }
+}
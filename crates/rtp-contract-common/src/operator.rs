@@ -0,0 +1,18 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    PublicKey,
+};
+
+/// Functions of the bank contract, which the access key of the bank's operator can call.
+pub const OPERATOR_KEY_METHODS: &str = "perform_trade,confirm_payment";
+
+/// Function call access key of the operator of a bank.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorKey {
+    pub public_key: PublicKey,
+    /// Amount in yoctoNEAR, which the key can spend on gas.
+    pub allowance: U128,
+}
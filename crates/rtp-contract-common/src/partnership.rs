@@ -0,0 +1,176 @@
+use crate::{NdfContract, PaymentLeg, Product, TradeDetails};
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Partnership {
+    pub bank_a: String,
+    pub bank_b: String,
+    pub terms: PartnershipTerms,
+    pub status: PartnershipStatus,
+}
+
+impl Partnership {
+    pub fn is_active(&self) -> bool {
+        self.status == PartnershipStatus::Active
+    }
+}
+
+/// Bilateral terms both banks agreed on when establishing a partnership.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PartnershipTerms {
+    pub products: Vec<Product>,
+    pub currencies: Vec<String>,
+    pub settlement_methods: Vec<String>,
+    pub matching_rule_set: String,
+}
+
+impl PartnershipTerms {
+    /// Returns the first trade attribute, which is not covered by these terms.
+    pub fn violation(&self, trade_details: &TradeDetails) -> Option<TermsViolation> {
+        if !self.products.contains(&trade_details.product) {
+            return Some(TermsViolation::Product(trade_details.product.clone()));
+        }
+        let currencies = [
+            Some(&trade_details.ccy),
+            Some(&trade_details.ccy1_payment_ccy),
+            trade_details.ccy2_payment_ccy.as_ref(),
+            trade_details.ndf.as_ref().map(|ndf| &ndf.settlement_ccy),
+        ];
+        if let Some(ccy) = currencies
+            .into_iter()
+            .flatten()
+            .find(|ccy| !self.currencies.contains(ccy))
+        {
+            return Some(TermsViolation::Currency(ccy.clone()));
+        }
+        if !self
+            .settlement_methods
+            .contains(&trade_details.settlement_method)
+        {
+            return Some(TermsViolation::SettlementMethod(
+                trade_details.settlement_method.clone(),
+            ));
+        }
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "status", content = "proposer")]
+pub enum PartnershipStatus {
+    /// Terms have been proposed by the bank with given bank ID
+    /// and are waiting for consent of the counterparty.
+    Proposed(String),
+    Active,
+}
+
+#[derive(Clone, Debug)]
+pub enum TermsViolation {
+    Product(Product),
+    Currency(String),
+    SettlementMethod(String),
+}
+
+/// Exposure of a partnership in a single currency.
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Exposure {
+    /// Maximum matched and unsettled amount. No limit is enforced, if not set.
+    pub limit: Option<f64>,
+    /// Amount of trades, which have been submitted, but not yet matched.
+    pub submitted: f64,
+    /// Amount of trades, which have been matched, but not yet settled.
+    pub matched: f64,
+}
+
+impl Exposure {
+    pub fn would_breach(&self, amount: f64) -> bool {
+        self.limit
+            .map(|limit| self.matched + amount > limit)
+            .unwrap_or_default()
+    }
+
+    pub fn utilisation(&self) -> Option<f64> {
+        self.limit
+            .map(|limit| if limit > 0. { self.matched / limit } else { 1. })
+    }
+}
+
+/// Trade as tracked by the factory for all partnership wide bookkeeping.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PartnershipTrade {
+    pub product: Product,
+    pub ccy: String,
+    pub amount: f64,
+    pub value_date: String,
+    /// Empty for NDFs until they are fixed.
+    pub legs: Vec<PaymentLeg>,
+    pub status: PartnershipTradeStatus,
+    /// ID of the netting cycle, which settles this trade.
+    pub netting_cycle: Option<u64>,
+    pub ndf: Option<NdfContract>,
+    /// Payment calendar of the trade, e.g. `NYLN`, which lifecycle value dates need to respect.
+    pub payment_calendar: String,
+}
+
+impl PartnershipTrade {
+    /// Whether the trade has neither been settled nor released.
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.status,
+            PartnershipTradeStatus::Settled | PartnershipTradeStatus::Released
+        )
+    }
+
+    /// New matched trade, which replaces this trade at a later value date and a new price.
+    /// The quote currency payment is recomputed from the base currency payment,
+    /// which is always the first payment of a currency pair.
+    pub fn rolled(&self, value_date: String, price: f32) -> Self {
+        let mut legs = self.legs.clone();
+        if let [base_leg, quote_leg] = legs.as_mut_slice() {
+            quote_leg.amount = base_leg.amount * price as f64;
+        }
+        Self {
+            value_date,
+            legs,
+            status: PartnershipTradeStatus::Matched,
+            netting_cycle: None,
+            ..self.clone()
+        }
+    }
+
+    /// New matched trade, which only pays the close-out amount of this trade at given value date.
+    pub fn closed_out(&self, close_out: PaymentLeg, value_date: String) -> Self {
+        Self {
+            ccy: close_out.ccy.clone(),
+            amount: close_out.amount,
+            value_date,
+            legs: vec![close_out],
+            status: PartnershipTradeStatus::Matched,
+            netting_cycle: None,
+            ndf: None,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PartnershipTradeStatus {
+    Submitted,
+    Matched,
+    /// Trade has not been settled before the cut-off on its value date.
+    MissedCutOff,
+    Settled,
+    /// Trade has been rejected, failed or rolled and no longer counts towards exposure.
+    Released,
+}
@@ -0,0 +1,41 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+use std::fmt;
+
+/// Scope of an emergency pause, which halts trading and settlement.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "scope")]
+pub enum PauseScope {
+    /// All banks and partnerships.
+    Global,
+    /// All trades, in which the bank is a counterparty.
+    Bank { bank_id: String },
+    /// All trades of the partnership.
+    Partnership { partnership_id: String },
+}
+
+impl fmt::Display for PauseScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseScope::Global => write!(f, "global"),
+            PauseScope::Bank { bank_id } => write!(f, "bank {bank_id}"),
+            PauseScope::Partnership { partnership_id } => {
+                write!(f, "partnership {partnership_id}")
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{Product, TradeDetails};
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Desks, i.e. booking accounts, legal entities and products, which a trader may trade.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TraderPermissions {
+    pub desks: Vec<String>,
+    pub legal_entity_ids: Vec<String>,
+    pub products: Vec<Product>,
+}
+
+/// Legal entities and products, which may be booked into a desk.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeskPermissions {
+    pub legal_entity_ids: Vec<String>,
+    pub products: Vec<Product>,
+}
+
+/// Reason, why the trader or desk of a trade are not permitted to trade it, if any.
+/// The desk of a trade is given by its `account`.
+pub fn permission_violation(
+    trade_details: &TradeDetails,
+    trader: Option<&TraderPermissions>,
+    desk: Option<&DeskPermissions>,
+) -> Option<String> {
+    let Some(trader) = trader else {
+        return Some(format!("Unknown trader {}", trade_details.trader_id));
+    };
+    let Some(desk) = desk else {
+        return Some(format!("Unknown desk {}", trade_details.account));
+    };
+
+    if !trader.desks.contains(&trade_details.account) {
+        Some(format!(
+            "Trader {} may not book into desk {}",
+            trade_details.trader_id, trade_details.account
+        ))
+    } else if !trader
+        .legal_entity_ids
+        .contains(&trade_details.legal_entity_id)
+        || !desk
+            .legal_entity_ids
+            .contains(&trade_details.legal_entity_id)
+    {
+        Some(format!(
+            "Legal entity {} not permitted",
+            trade_details.legal_entity_id
+        ))
+    } else if !trader.products.contains(&trade_details.product)
+        || !desk.products.contains(&trade_details.product)
+    {
+        Some(format!("Product {:?} not permitted", trade_details.product))
+    } else {
+        None
+    }
+}
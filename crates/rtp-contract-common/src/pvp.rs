@@ -0,0 +1,46 @@
+use crate::PaymentLeg;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+/// NEP-141 token, which represents tokenized cash of a currency.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CurrencyToken {
+    pub token_id: AccountId,
+    pub decimals: u8,
+}
+
+impl CurrencyToken {
+    /// Converts an amount of the currency into the smallest unit of the token.
+    pub fn to_token_amount(&self, amount: f64) -> u128 {
+        (amount * 10f64.powi(self.decimals as i32)).round() as u128
+    }
+}
+
+/// Tokenized cash held in escrow for a payment-versus-payment settlement of a trade.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PvpEscrow {
+    pub legs: Vec<EscrowLeg>,
+}
+
+impl PvpEscrow {
+    pub fn is_funded(&self) -> bool {
+        self.legs.iter().all(|leg| leg.deposited == leg.required)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowLeg {
+    pub leg: PaymentLeg,
+    pub token_id: AccountId,
+    pub required: U128,
+    pub deposited: U128,
+    /// Account, which deposited the tokens. Tokens of the opposite leg will be paid out to it.
+    pub depositor: Option<AccountId>,
+}
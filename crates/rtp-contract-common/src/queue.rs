@@ -0,0 +1,294 @@
+use crate::PaymentLeg;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+};
+use std::collections::BTreeMap;
+
+/// Liquidity of banks by bank ID and currency, in the smallest unit of the currency token.
+pub type Liquidity = BTreeMap<(String, String), u128>;
+
+/// Trade waiting in the settlement queue for sufficient liquidity of its payers.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedSettlement {
+    /// Settlements with higher priority are settled first.
+    pub priority: u8,
+    /// Position in the queue among settlements with the same priority.
+    pub sequence: u64,
+    pub payments: Vec<QueuedPayment>,
+}
+
+impl QueuedSettlement {
+    /// Settlements with a lower queue order are settled first.
+    pub fn queue_order(&self) -> (u8, u64) {
+        (u8::MAX - self.priority, self.sequence)
+    }
+
+    /// Applies all payments to the liquidity, if every payer can afford them.
+    pub fn try_settle(&self, liquidity: &mut Liquidity) -> bool {
+        let mut required: BTreeMap<(&String, &String), u128> = BTreeMap::new();
+        for payment in self.payments.iter() {
+            *required
+                .entry((&payment.leg.payer, &payment.leg.ccy))
+                .or_default() += payment.amount.0;
+        }
+        let can_settle = required.into_iter().all(|((payer, ccy), amount)| {
+            liquidity
+                .get(&(payer.clone(), ccy.clone()))
+                .map(|available| *available >= amount)
+                .unwrap_or_default()
+        });
+        if can_settle {
+            for payment in self.payments.iter() {
+                let leg = &payment.leg;
+                *liquidity
+                    .entry((leg.payer.clone(), leg.ccy.clone()))
+                    .or_default() -= payment.amount.0;
+                *liquidity
+                    .entry((leg.receiver.clone(), leg.ccy.clone()))
+                    .or_default() += payment.amount.0;
+            }
+        }
+        can_settle
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedPayment {
+    pub leg: PaymentLeg,
+    /// Amount in the smallest unit of the currency token.
+    pub amount: U128,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "status", content = "reason")]
+pub enum QueueStatus {
+    /// Payment is waiting in the queue for given reason.
+    Queued(String),
+    Settled(String),
+    Cancelled,
+}
+
+/// Settles queued settlements one by one in queue order, as long as liquidity suffices.
+/// Liquidity received from a settlement can be used by every following settlement.
+/// Returns the keys of all settled settlements.
+pub fn settle_queue<K: Clone>(
+    queue: &[(K, QueuedSettlement)],
+    liquidity: &mut Liquidity,
+) -> Vec<K> {
+    let order = queue_order(queue);
+    let mut settled = vec![false; queue.len()];
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for index in order.iter().copied() {
+            if !settled[index] && queue[index].1.try_settle(liquidity) {
+                settled[index] = true;
+                progress = true;
+            }
+        }
+    }
+    queue
+        .iter()
+        .zip(settled)
+        .filter(|(_, settled)| *settled)
+        .map(|(entry, _)| entry.0.clone())
+        .collect()
+}
+
+/// Finds an offsetting set of queued settlements, which can be settled simultaneously,
+/// even though none of them can be settled on its own.
+/// Starting with the whole queue, the last settlement in queue order of any bank with
+/// insufficient liquidity gets removed, until every bank can cover its net outflow.
+/// Settles and returns the keys of the remaining settlements.
+pub fn resolve_gridlock<K: Clone>(
+    queue: &[(K, QueuedSettlement)],
+    liquidity: &mut Liquidity,
+) -> Vec<K> {
+    let order = queue_order(queue);
+    let mut selected = vec![true; queue.len()];
+    loop {
+        let mut balances: BTreeMap<(&String, &String), i128> = liquidity
+            .iter()
+            .map(|((bank_id, ccy), amount)| ((bank_id, ccy), *amount as i128))
+            .collect();
+        for (index, (_, settlement)) in queue.iter().enumerate() {
+            if !selected[index] {
+                continue;
+            }
+            for payment in settlement.payments.iter() {
+                let leg = &payment.leg;
+                *balances.entry((&leg.payer, &leg.ccy)).or_default() -= payment.amount.0 as i128;
+                *balances.entry((&leg.receiver, &leg.ccy)).or_default() += payment.amount.0 as i128;
+            }
+        }
+        let Some(((bank_id, ccy), _)) = balances.into_iter().find(|(_, balance)| *balance < 0)
+        else {
+            break;
+        };
+
+        let removed = order
+            .iter()
+            .rev()
+            .copied()
+            .find(|index| {
+                selected[*index]
+                    && queue[*index]
+                        .1
+                        .payments
+                        .iter()
+                        .any(|payment| payment.leg.payer == *bank_id && payment.leg.ccy == *ccy)
+            })
+            .unwrap();
+        selected[removed] = false;
+    }
+
+    let resolved: Vec<_> = queue
+        .iter()
+        .zip(selected)
+        .filter(|(_, selected)| *selected)
+        .map(|(entry, _)| entry)
+        .collect();
+    // credit all incoming payments first, because settlements only offset each other in total
+    for (_, settlement) in resolved.iter() {
+        for payment in settlement.payments.iter() {
+            *liquidity
+                .entry((payment.leg.receiver.clone(), payment.leg.ccy.clone()))
+                .or_default() += payment.amount.0;
+        }
+    }
+    for (_, settlement) in resolved.iter() {
+        for payment in settlement.payments.iter() {
+            *liquidity
+                .entry((payment.leg.payer.clone(), payment.leg.ccy.clone()))
+                .or_default() -= payment.amount.0;
+        }
+    }
+    resolved.into_iter().map(|(key, _)| key.clone()).collect()
+}
+
+/// Indices of the queued settlements in queue order.
+fn queue_order<K>(queue: &[(K, QueuedSettlement)]) -> Vec<usize> {
+    let mut order: Vec<_> = (0..queue.len()).collect();
+    order.sort_by_key(|index| queue[*index].1.queue_order());
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settlement(
+        priority: u8,
+        sequence: u64,
+        payments: &[(&str, &str, u128)],
+    ) -> QueuedSettlement {
+        QueuedSettlement {
+            priority,
+            sequence,
+            payments: payments
+                .iter()
+                .map(|(payer, receiver, amount)| QueuedPayment {
+                    leg: PaymentLeg {
+                        payer: payer.to_string(),
+                        receiver: receiver.to_string(),
+                        ccy: "EUR".to_string(),
+                        amount: *amount as f64,
+                    },
+                    amount: U128(*amount),
+                })
+                .collect(),
+        }
+    }
+
+    fn liquidity(balances: &[(&str, u128)]) -> Liquidity {
+        balances
+            .iter()
+            .map(|(bank_id, amount)| ((bank_id.to_string(), "EUR".to_string()), *amount))
+            .collect()
+    }
+
+    #[test]
+    fn test_queue_order() {
+        let urgent = settlement(1, 2, &[]);
+        let normal = settlement(0, 1, &[]);
+        let later = settlement(0, 3, &[]);
+        assert!(urgent.queue_order() < normal.queue_order());
+        assert!(normal.queue_order() < later.queue_order());
+    }
+
+    #[test]
+    fn test_settle_queue_in_queue_order() {
+        let queue = [
+            ("low", settlement(0, 1, &[("a", "b", 100)])),
+            ("high", settlement(1, 2, &[("a", "c", 100)])),
+        ];
+        let mut liquidity = liquidity(&[("a", 100)]);
+
+        assert_eq!(settle_queue(&queue, &mut liquidity), vec!["high"]);
+        assert_eq!(liquidity[&("a".to_string(), "EUR".to_string())], 0);
+        assert_eq!(liquidity[&("c".to_string(), "EUR".to_string())], 100);
+    }
+
+    #[test]
+    fn test_settle_queue_with_received_liquidity() {
+        // b can only pay c after it received the payment of a
+        let queue = [
+            ("b->c", settlement(0, 1, &[("b", "c", 100)])),
+            ("a->b", settlement(0, 2, &[("a", "b", 100)])),
+        ];
+        let mut liquidity = liquidity(&[("a", 100)]);
+
+        assert_eq!(settle_queue(&queue, &mut liquidity), vec!["b->c", "a->b"]);
+        assert_eq!(liquidity[&("c".to_string(), "EUR".to_string())], 100);
+    }
+
+    #[test]
+    fn test_resolve_gridlock() {
+        // a and b owe each other, but neither can pay on its own
+        let queue = [
+            ("a->b", settlement(0, 1, &[("a", "b", 100)])),
+            ("b->a", settlement(0, 2, &[("b", "a", 80)])),
+        ];
+        let mut liquidity = liquidity(&[("a", 50)]);
+
+        assert!(settle_queue(&queue, &mut liquidity.clone()).is_empty());
+        assert_eq!(
+            resolve_gridlock(&queue, &mut liquidity),
+            vec!["a->b", "b->a"]
+        );
+        assert_eq!(liquidity[&("a".to_string(), "EUR".to_string())], 30);
+        assert_eq!(liquidity[&("b".to_string(), "EUR".to_string())], 20);
+    }
+
+    #[test]
+    fn test_resolve_gridlock_removes_last_settlement() {
+        // a cannot cover both of its payments, so its last one in queue order is left in the queue
+        let queue = [
+            ("a->b", settlement(0, 1, &[("a", "b", 100)])),
+            ("b->a", settlement(0, 2, &[("b", "a", 100)])),
+            ("a->c", settlement(0, 3, &[("a", "c", 100)])),
+        ];
+        let mut liquidity = liquidity(&[]);
+
+        assert_eq!(
+            resolve_gridlock(&queue, &mut liquidity),
+            vec!["a->b", "b->a"]
+        );
+        assert_eq!(liquidity[&("a".to_string(), "EUR".to_string())], 0);
+        assert_eq!(liquidity[&("b".to_string(), "EUR".to_string())], 0);
+    }
+
+    #[test]
+    fn test_resolve_gridlock_without_offset() {
+        let queue = [("a->b", settlement(0, 1, &[("a", "b", 100)]))];
+        let mut liquidity = liquidity(&[("a", 50)]);
+
+        assert!(resolve_gridlock(&queue, &mut liquidity).is_empty());
+        assert_eq!(liquidity[&("a".to_string(), "EUR".to_string())], 50);
+    }
+}
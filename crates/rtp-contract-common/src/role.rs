@@ -0,0 +1,30 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Permission of an account to call restricted factory functions.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    /// Submits fixing rates of NDFs.
+    RateProvider,
+    /// Maintains the screening list of counterparties.
+    Compliance,
+    /// Pauses and unpauses trading in an emergency.
+    Pauser,
+    /// Sets the matching and payment statuses of trades.
+    Matcher,
+}
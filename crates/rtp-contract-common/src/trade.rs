@@ -1,5 +1,7 @@
+use crate::{LifecycleAction, NdfContract, NdfFixing, NdfTerms, PaymentLeg};
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
     serde::{Deserialize, Serialize},
 };
 
@@ -11,6 +13,111 @@ pub struct Trade {
     pub matching_status: MatchingStatus,
     pub payment_status: PaymentStatus,
     pub payments: Payments,
+    /// Cash-settlement of an NDF, as soon as the fixing rate is known.
+    pub ndf_fixing: Option<NdfFixing>,
+    /// Lifecycle operation, which rolled or terminated this trade.
+    pub lifecycle: Option<LifecycleAction>,
+    /// Reason, why the trade is held back from matching.
+    pub compliance_hold: Option<String>,
+    /// Only set, if the trade requires four-eyes approval.
+    pub approval_status: Option<ApprovalStatus>,
+    /// When the trade reached the chain.
+    pub submitted_at: BlockTime,
+    /// When the trade has been confirmed by matching.
+    pub matched_at: Option<BlockTime>,
+    /// When the payments of the trade have been confirmed.
+    pub paid_at: Option<BlockTime>,
+}
+
+/// Block height and timestamp, at which something happened on-chain.
+/// Unlike the timestamps of `TradeDetails`, these do not rely on client clocks.
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlockTime {
+    pub block_height: u64,
+    pub timestamp_ms: u64,
+}
+
+impl BlockTime {
+    /// Block time of the current block.
+    pub fn now() -> Self {
+        Self {
+            block_height: env::block_height(),
+            timestamp_ms: env::block_timestamp_ms(),
+        }
+    }
+}
+
+/// Latencies of a trade in milliseconds, which are measured from its submission.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TradeLatency {
+    pub matching_ms: Option<u64>,
+    pub payment_ms: Option<u64>,
+}
+
+/// Maximum number of trades in a batch, which can be stored within the gas limit of a transaction.
+pub const MAX_TRADE_BATCH_SIZE: u32 = 50;
+
+/// Outcome of a trade submitted as part of a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TradeSubmission {
+    pub trade_id: String,
+    /// Reason, why the trade was rejected.
+    pub error: Option<String>,
+}
+
+/// Maximum number of status updates in a batch, which can be applied within the gas limit of a transaction.
+pub const MAX_STATUS_BATCH_SIZE: u32 = 50;
+
+/// Matching status of a trade of a partnership, which is updated as part of a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MatchingStatusUpdate {
+    pub partnership_id: String,
+    pub bank_a_id: String,
+    pub bank_b_id: String,
+    pub trade_id: String,
+    pub matching_status: MatchingStatus,
+}
+
+/// Payment status of a trade of a partnership, which is updated as part of a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentStatusUpdate {
+    pub partnership_id: String,
+    pub bank_a_id: String,
+    pub bank_b_id: String,
+    pub trade_id: String,
+    pub payment_status: PaymentStatus,
+}
+
+/// Outcome of a status update, which was part of a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StatusUpdateOutcome {
+    pub partnership_id: String,
+    pub trade_id: String,
+    /// Reason, why the update was not applied.
+    pub error: Option<String>,
+}
+
+impl Trade {
+    pub fn is_paid(&self) -> bool {
+        matches!(self.payment_status, PaymentStatus::Confirmed(_))
+    }
+
+    pub fn latency(&self) -> TradeLatency {
+        let since_submission =
+            |block_time: &BlockTime| block_time.timestamp_ms - self.submitted_at.timestamp_ms;
+        TradeLatency {
+            matching_ms: self.matched_at.as_ref().map(since_submission),
+            payment_ms: self.paid_at.as_ref().map(since_submission),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -87,6 +194,10 @@ pub struct TradeDetails {
     pub ccy2_payment_date_u: Option<String>,
     pub secondary_trade_id: String,
     pub source_trade_id: String,
+    /// Only set for NDFs.
+    pub ndf: Option<NdfTerms>,
+    /// Only set for swaps, which consist of a near and a far leg linked via `secondary_trade_id`.
+    pub swap_leg: Option<SwapLeg>,
 }
 
 impl Default for TradeDetails {
@@ -162,24 +273,135 @@ impl Default for TradeDetails {
             ccy2_payment_date_u: None,
             secondary_trade_id: Default::default(),
             source_trade_id: Default::default(),
+            ndf: None,
+            swap_leg: None,
         }
     }
 }
 
 impl TradeDetails {
+    /// Near leg of a swap. The far leg is linked via `secondary_trade_id`.
     pub fn default_swap() -> Self {
         Self {
-            ccy2_value_date: Some("12.12.2023".to_string()),
-            ccy2_discount_factor: Some(0.15),
-            ccy2_payer_party_id: Some("73355".to_string()),
-            ccy2_payment_amt: Some(1_150.),
-            ccy2_payment_ccy: Some("USD".to_string()),
+            product: Product::Swap,
+            secondary_trade_id: "far_trade_id".to_string(),
+            swap_leg: Some(SwapLeg::Near),
             ..Default::default()
         }
     }
+
+    /// Far leg of a swap, which reverses the near leg at a later value date.
+    pub fn default_swap_far() -> Self {
+        Self {
+            trade_id: "far_trade_id".to_string(),
+            product: Product::Swap,
+            side: Side::Sell,
+            price: 1.16,
+            ccy1_value_date: "11.01.2024".to_string(),
+            ccy1_payment_date_u: "15.01.2024".to_string(),
+            secondary_trade_id: "trade_id".to_string(),
+            swap_leg: Some(SwapLeg::Far),
+            ..Default::default()
+        }
+    }
+
+    /// Whether this swap leg is linked to another leg.
+    pub fn is_valid_swap_leg(&self) -> bool {
+        self.swap_leg.is_some()
+            && !self.secondary_trade_id.is_empty()
+            && self.secondary_trade_id != self.trade_id
+    }
+
+    pub fn default_ndf() -> Self {
+        Self {
+            instrument_id: "USD/BRL".to_string(),
+            product: Product::Ndf,
+            price: 5.,
+            ccy: "USD".to_string(),
+            dealt_ccy: "USD".to_string(),
+            ccy1_payment_ccy: "USD".to_string(),
+            ndf: Some(NdfTerms {
+                fixing_date: "09.12.2023".to_string(),
+                fixing_source: "PTAX".to_string(),
+                settlement_ccy: "USD".to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Payments, which need to be made to settle this trade.
+    ///
+    /// The seller delivers the notional amount in the base currency of the instrument
+    /// and the buyer pays the notional amount multiplied by the price in the quote currency.
+    /// If the instrument is not a currency pair, only the notional amount is delivered.
+    /// NDFs have no payments until they are fixed.
+    pub fn payment_legs(&self, bank_id: &str, counterparty_id: &str) -> Vec<PaymentLeg> {
+        if self.product == Product::Ndf {
+            return vec![];
+        }
+        let (buyer, seller) = self.buyer_and_seller(bank_id, counterparty_id);
+        let notional_amount = self.notional_amount as f64;
+        match self.instrument_id.split_once('/') {
+            Some((base_ccy, quote_ccy)) => vec![
+                PaymentLeg {
+                    payer: seller.to_string(),
+                    receiver: buyer.to_string(),
+                    ccy: base_ccy.to_string(),
+                    amount: notional_amount,
+                },
+                PaymentLeg {
+                    payer: buyer.to_string(),
+                    receiver: seller.to_string(),
+                    ccy: quote_ccy.to_string(),
+                    amount: notional_amount * self.price as f64,
+                },
+            ],
+            None => vec![PaymentLeg {
+                payer: seller.to_string(),
+                receiver: buyer.to_string(),
+                ccy: self.ccy.clone(),
+                amount: notional_amount,
+            }],
+        }
+    }
+
+    /// NDF contract, if this trade is an NDF with valid NDF terms.
+    /// The settlement currency needs to be either the base or the quote currency of the instrument.
+    pub fn ndf_contract(&self, bank_id: &str, counterparty_id: &str) -> Option<NdfContract> {
+        if self.product != Product::Ndf {
+            return None;
+        }
+        let terms = self.ndf.clone()?;
+        let (base_ccy, quote_ccy) = self.instrument_id.split_once('/')?;
+        if terms.settlement_ccy != base_ccy && terms.settlement_ccy != quote_ccy {
+            return None;
+        }
+        let (buyer, seller) = self.buyer_and_seller(bank_id, counterparty_id);
+        Some(NdfContract {
+            buyer: buyer.to_string(),
+            seller: seller.to_string(),
+            base_ccy: base_ccy.to_string(),
+            quote_ccy: quote_ccy.to_string(),
+            notional_amount: self.notional_amount as f64,
+            contract_rate: self.price as f64,
+            terms,
+            fixing: None,
+        })
+    }
+
+    fn buyer_and_seller<'a>(
+        &self,
+        bank_id: &'a str,
+        counterparty_id: &'a str,
+    ) -> (&'a str, &'a str) {
+        match self.side {
+            Side::Buy => (bank_id, counterparty_id),
+            Side::Sell => (counterparty_id, bank_id),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Product {
@@ -196,6 +418,13 @@ pub enum Side {
     Sell,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SwapLeg {
+    Near,
+    Far,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Settlement {
@@ -203,6 +432,23 @@ pub enum Settlement {
     T(u16),
 }
 
+/// Four-eyes approval of a trade, which exceeds the approval threshold of its currency.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "status")]
+pub enum ApprovalStatus {
+    /// Account IDs of the submitter and the approver need to differ.
+    AwaitingApproval {
+        submitted_by: String,
+    },
+    Approved {
+        approver: String,
+    },
+    Rejected {
+        approver: String,
+    },
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(tag = "status", content = "message")]
@@ -221,6 +467,8 @@ pub enum PaymentStatus {
     Confirmed(String),
     Rejected(String),
     Error,
+    /// Payment has not been made before the cut-off on the value date.
+    MissedCutOff(String),
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
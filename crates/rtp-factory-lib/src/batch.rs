@@ -0,0 +1,364 @@
+use crate::{rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId, Promise, PromiseOrValue, PromiseResult};
+use rtp_contract_common::{
+    BlockTime, MatchingStatus, MatchingStatusUpdate, PaymentStatus, PaymentStatusUpdate, Role,
+    RtpEvent, StatusUpdateOutcome, MAX_STATUS_BATCH_SIZE,
+};
+use std::collections::BTreeMap;
+
+#[near_bindgen]
+impl Contract {
+    /// Update the matching status of several trades at once.
+    /// Every involved bank contract receives all of its updates with a single call.
+    /// Returns the outcome of every update in the order of the updates.
+    #[handle_result]
+    pub fn set_matching_statuses(
+        &mut self,
+        updates: Vec<MatchingStatusUpdate>,
+    ) -> Result<PromiseOrValue<Vec<StatusUpdateOutcome>>, ContractError> {
+        self.assert_role(Role::Matcher)?;
+        if updates.len() > MAX_STATUS_BATCH_SIZE as usize {
+            return Err(ContractError::StatusBatchTooLarge(MAX_STATUS_BATCH_SIZE));
+        }
+
+        let mut outcomes = Vec::with_capacity(updates.len());
+        let mut accepted = Vec::new();
+        for update in updates {
+            let error = self
+                .check_status_update(&update.partnership_id, &update.bank_a_id, &update.bank_b_id)
+                .err()
+                .map(|err| err.to_string());
+            outcomes.push(StatusUpdateOutcome {
+                partnership_id: update.partnership_id.clone(),
+                trade_id: update.trade_id.clone(),
+                error: error.clone(),
+            });
+            if error.is_none() {
+                let matching_status = self.match_exposure(
+                    &update.partnership_id,
+                    &update.trade_id,
+                    update.matching_status.clone(),
+                );
+                accepted.push(MatchingStatusUpdate {
+                    matching_status,
+                    ..update
+                });
+            }
+        }
+        if accepted.is_empty() {
+            return Ok(PromiseOrValue::Value(self.on_set_matching_statuses(
+                vec![],
+                accepted,
+                outcomes,
+            )));
+        }
+
+        let updates_by_bank = group_by_bank(accepted.iter().map(|update| {
+            (
+                [update.bank_a_id.as_str(), update.bank_b_id.as_str()],
+                update.trade_id.as_str(),
+                update.matching_status.clone(),
+            )
+        }));
+        let bank_ids = updates_by_bank.keys().cloned().collect();
+        let factory_account_id = env::current_account_id();
+
+        Ok(PromiseOrValue::Promise(
+            updates_by_bank
+                .into_iter()
+                .map(|(bank_id, updates)| {
+                    let account_id: AccountId =
+                        format!("{bank_id}.{factory_account_id}").parse().unwrap();
+                    rtp::ext(account_id)
+                        .with_unused_gas_weight(1)
+                        .set_matching_statuses(updates)
+                })
+                .reduce(Promise::and)
+                .unwrap()
+                .then(
+                    Self::ext(factory_account_id.clone())
+                        .on_set_matching_statuses(bank_ids, accepted, outcomes),
+                ),
+        ))
+    }
+
+    /// Trades, which failed to be updated in any bank contract, are set to `Error` in both.
+    #[private]
+    pub fn on_set_matching_statuses(
+        &mut self,
+        bank_ids: Vec<String>,
+        updates: Vec<MatchingStatusUpdate>,
+        outcomes: Vec<StatusUpdateOutcome>,
+    ) -> Vec<StatusUpdateOutcome> {
+        let errors = bank_errors(
+            &bank_ids,
+            updates
+                .iter()
+                .map(|update| [update.bank_a_id.as_str(), update.bank_b_id.as_str()]),
+        );
+        let mut applied = updates.into_iter().zip(errors);
+        let mut failed = Vec::new();
+        let mut merged = Vec::with_capacity(outcomes.len());
+        for outcome in outcomes {
+            if outcome.error.is_some() {
+                merged.push(outcome);
+                continue;
+            }
+            let (update, error) = applied.next().unwrap();
+            let matching_status = if error.is_some() {
+                self.release_exposure(&update.partnership_id, &update.trade_id);
+                failed.push(update.clone());
+                MatchingStatus::Error
+            } else {
+                update.matching_status
+            };
+            let event = RtpEvent::SetMatchingStatus {
+                partnership_id: update.partnership_id,
+                trade_id: update.trade_id,
+                matching_status,
+                block_time: BlockTime::now(),
+            };
+            event.emit();
+
+            merged.push(StatusUpdateOutcome { error, ..outcome });
+        }
+
+        let factory_account_id = env::current_account_id();
+        let resets = group_by_bank(failed.iter().map(|update| {
+            (
+                [update.bank_a_id.as_str(), update.bank_b_id.as_str()],
+                update.trade_id.as_str(),
+                MatchingStatus::Error,
+            )
+        }));
+        for (bank_id, updates) in resets {
+            let account_id: AccountId = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+            rtp::ext(account_id).set_matching_statuses(updates);
+        }
+
+        let event = RtpEvent::SetMatchingStatuses {
+            outcomes: merged.clone(),
+            block_time: BlockTime::now(),
+        };
+        event.emit();
+
+        merged
+    }
+
+    /// Update the payment status of several trades at once.
+    /// Every involved bank contract receives all of its updates with a single call.
+    /// Returns the outcome of every update in the order of the updates.
+    #[handle_result]
+    pub fn set_payment_statuses(
+        &mut self,
+        updates: Vec<PaymentStatusUpdate>,
+    ) -> Result<PromiseOrValue<Vec<StatusUpdateOutcome>>, ContractError> {
+        self.assert_role(Role::Matcher)?;
+        if updates.len() > MAX_STATUS_BATCH_SIZE as usize {
+            return Err(ContractError::StatusBatchTooLarge(MAX_STATUS_BATCH_SIZE));
+        }
+
+        let mut outcomes = Vec::with_capacity(updates.len());
+        let mut accepted = Vec::new();
+        for update in updates {
+            let error = self
+                .check_status_update(&update.partnership_id, &update.bank_a_id, &update.bank_b_id)
+                .and_then(|_| {
+                    self.assert_payment_status_window(
+                        &update.partnership_id,
+                        &update.trade_id,
+                        &update.payment_status,
+                    )
+                })
+                .err()
+                .map(|err| err.to_string());
+            outcomes.push(StatusUpdateOutcome {
+                partnership_id: update.partnership_id.clone(),
+                trade_id: update.trade_id.clone(),
+                error: error.clone(),
+            });
+            if error.is_none() {
+                self.settle_exposure(
+                    &update.partnership_id,
+                    &update.trade_id,
+                    &update.payment_status,
+                );
+                accepted.push(update);
+            }
+        }
+        if accepted.is_empty() {
+            return Ok(PromiseOrValue::Value(self.on_set_payment_statuses(
+                vec![],
+                accepted,
+                outcomes,
+            )));
+        }
+
+        let updates_by_bank = group_by_bank(accepted.iter().map(|update| {
+            (
+                [update.bank_a_id.as_str(), update.bank_b_id.as_str()],
+                update.trade_id.as_str(),
+                update.payment_status.clone(),
+            )
+        }));
+        let bank_ids = updates_by_bank.keys().cloned().collect();
+        let factory_account_id = env::current_account_id();
+
+        Ok(PromiseOrValue::Promise(
+            updates_by_bank
+                .into_iter()
+                .map(|(bank_id, updates)| {
+                    let account_id: AccountId =
+                        format!("{bank_id}.{factory_account_id}").parse().unwrap();
+                    rtp::ext(account_id)
+                        .with_unused_gas_weight(1)
+                        .set_payment_statuses(updates)
+                })
+                .reduce(Promise::and)
+                .unwrap()
+                .then(
+                    Self::ext(factory_account_id.clone())
+                        .on_set_payment_statuses(bank_ids, accepted, outcomes),
+                ),
+        ))
+    }
+
+    /// Trades, which failed to be updated in any bank contract, are set to `Error` in both.
+    #[private]
+    pub fn on_set_payment_statuses(
+        &mut self,
+        bank_ids: Vec<String>,
+        updates: Vec<PaymentStatusUpdate>,
+        outcomes: Vec<StatusUpdateOutcome>,
+    ) -> Vec<StatusUpdateOutcome> {
+        let errors = bank_errors(
+            &bank_ids,
+            updates
+                .iter()
+                .map(|update| [update.bank_a_id.as_str(), update.bank_b_id.as_str()]),
+        );
+        let mut applied = updates.into_iter().zip(errors);
+        let mut failed = Vec::new();
+        let mut merged = Vec::with_capacity(outcomes.len());
+        for outcome in outcomes {
+            if outcome.error.is_some() {
+                merged.push(outcome);
+                continue;
+            }
+            let (update, error) = applied.next().unwrap();
+            let payment_status = if error.is_some() {
+                self.settle_exposure(
+                    &update.partnership_id,
+                    &update.trade_id,
+                    &PaymentStatus::Error,
+                );
+                failed.push(update.clone());
+                PaymentStatus::Error
+            } else {
+                update.payment_status
+            };
+            let event = RtpEvent::SetPaymentStatus {
+                partnership_id: update.partnership_id,
+                trade_id: update.trade_id,
+                payment_status,
+                block_time: BlockTime::now(),
+            };
+            event.emit();
+
+            merged.push(StatusUpdateOutcome { error, ..outcome });
+        }
+
+        let factory_account_id = env::current_account_id();
+        let resets = group_by_bank(failed.iter().map(|update| {
+            (
+                [update.bank_a_id.as_str(), update.bank_b_id.as_str()],
+                update.trade_id.as_str(),
+                PaymentStatus::Error,
+            )
+        }));
+        for (bank_id, updates) in resets {
+            let account_id: AccountId = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+            rtp::ext(account_id).set_payment_statuses(updates);
+        }
+
+        let event = RtpEvent::SetPaymentStatuses {
+            outcomes: merged.clone(),
+            block_time: BlockTime::now(),
+        };
+        event.emit();
+
+        merged
+    }
+
+    pub fn get_max_status_batch_size(&self) -> u32 {
+        MAX_STATUS_BATCH_SIZE
+    }
+}
+
+impl Contract {
+    /// Same checks as for a single status update, which rejects the whole call.
+    fn check_status_update(
+        &self,
+        partnership_id: &str,
+        bank_a_id: &str,
+        bank_b_id: &str,
+    ) -> Result<(), ContractError> {
+        if !self.bank_ids.contains(bank_a_id) || !self.bank_ids.contains(bank_b_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+        self.assert_not_paused(&[bank_a_id, bank_b_id], Some(partnership_id))
+    }
+}
+
+/// Collects the status updates of trades for the bank contracts of both counterparties.
+fn group_by_bank<'a, S: Clone>(
+    updates: impl Iterator<Item = ([&'a str; 2], &'a str, S)>,
+) -> BTreeMap<String, Vec<(String, S)>> {
+    let mut updates_by_bank: BTreeMap<String, Vec<(String, S)>> = BTreeMap::new();
+    for (bank_ids, trade_id, status) in updates {
+        for bank_id in bank_ids {
+            updates_by_bank
+                .entry(bank_id.to_string())
+                .or_default()
+                .push((trade_id.to_string(), status.clone()));
+        }
+    }
+    updates_by_bank
+}
+
+/// Error of every update, as reported by the bank contracts of both counterparties.
+/// The promise results have to be in the order of `bank_ids`.
+fn bank_errors<'a>(
+    bank_ids: &[String],
+    updates: impl Iterator<Item = [&'a str; 2]>,
+) -> Vec<Option<String>> {
+    let mut results: BTreeMap<&str, Option<std::vec::IntoIter<Option<String>>>> = bank_ids
+        .iter()
+        .enumerate()
+        .map(|(index, bank_id)| {
+            let errors = match env::promise_result(index as u64) {
+                PromiseResult::Successful(data) => {
+                    serde_json::from_slice::<Vec<Option<String>>>(&data)
+                        .ok()
+                        .map(Vec::into_iter)
+                }
+                _ => None,
+            };
+            (bank_id.as_str(), errors)
+        })
+        .collect();
+
+    updates
+        .map(|bank_ids| {
+            let mut error = None;
+            for bank_id in bank_ids {
+                let bank_error = match results.get_mut(bank_id) {
+                    Some(Some(errors)) => errors.next().flatten(),
+                    _ => Some("Bank contract failed to apply the batch".to_string()),
+                };
+                error = error.or(bank_error);
+            }
+            error
+        })
+        .collect()
+}
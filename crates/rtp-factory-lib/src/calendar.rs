@@ -0,0 +1,123 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::near_bindgen;
+use rtp_contract_common::{BusinessCalendar, Date, Settlement, TradeDetails};
+use std::collections::BTreeSet;
+
+#[near_bindgen]
+impl Contract {
+    /// Set the holidays of a financial centre, e.g. `NY` or `LN`, in the `DD.MM.YYYY` format.
+    /// Removes the holidays, if `holidays` is empty.
+    #[handle_result]
+    pub fn set_holidays(
+        &mut self,
+        financial_centre: String,
+        holidays: Vec<String>,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_holidays(financial_centre, holidays)
+    }
+
+    pub fn get_holidays(&self, financial_centre: String) -> Vec<String> {
+        self.holidays
+            .get(&financial_centre.to_uppercase())
+            .map(|holidays| holidays.iter().map(Date::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    #[handle_result]
+    pub fn is_business_day(
+        &self,
+        payment_calendar: String,
+        date: String,
+    ) -> Result<bool, ContractError> {
+        let date = parse_date(&date)?;
+        Ok(self
+            .business_calendar(&payment_calendar)
+            .is_business_day(&date))
+    }
+
+    /// Resolve the value date of a trade executed at `trade_date` with given settlement cycle.
+    #[handle_result]
+    pub fn get_value_date(
+        &self,
+        payment_calendar: String,
+        trade_date: String,
+        settlement: Settlement,
+    ) -> Result<String, ContractError> {
+        let trade_date = parse_date(&trade_date)?;
+        Ok(self
+            .business_calendar(&payment_calendar)
+            .value_date(&trade_date, &settlement)
+            .to_string())
+    }
+}
+
+impl Contract {
+    pub(crate) fn apply_holidays(
+        &mut self,
+        financial_centre: String,
+        holidays: Vec<String>,
+    ) -> Result<(), ContractError> {
+        let holidays: BTreeSet<Date> = holidays
+            .iter()
+            .map(|holiday| parse_date(holiday))
+            .collect::<Result<_, _>>()?;
+        let financial_centre = financial_centre.to_uppercase();
+        if holidays.is_empty() {
+            self.holidays.remove(&financial_centre);
+        } else {
+            self.holidays.insert(financial_centre, holidays);
+        }
+
+        Ok(())
+    }
+
+    /// Combined calendar of all financial centres of a payment calendar like `NYLN`.
+    /// Financial centres without holidays only exclude weekends.
+    pub(crate) fn business_calendar(&self, payment_calendar: &str) -> BusinessCalendar {
+        let holidays = BusinessCalendar::financial_centres(payment_calendar)
+            .iter()
+            .filter_map(|centre| self.holidays.get(centre))
+            .flatten()
+            .copied()
+            .collect();
+        BusinessCalendar { holidays }
+    }
+
+    /// All value and payment dates of a trade need to be business days of its payment calendar.
+    pub(crate) fn validate_dates(&self, trade_details: &TradeDetails) -> Result<(), ContractError> {
+        let dates = [
+            Some(&trade_details.ccy1_value_date),
+            trade_details.ccy2_value_date.as_ref(),
+            Some(&trade_details.ccy1_payment_date_u),
+            trade_details.ccy2_payment_date_u.as_ref(),
+        ];
+        for date in dates.into_iter().flatten() {
+            self.validate_value_date(&trade_details.payment_calendar, date)?;
+        }
+
+        Ok(())
+    }
+
+    /// A single value date needs to be a business day of given payment calendar.
+    pub(crate) fn validate_value_date(
+        &self,
+        payment_calendar: &str,
+        value_date: &str,
+    ) -> Result<Date, ContractError> {
+        let date = parse_date(value_date)?;
+        if !self
+            .business_calendar(payment_calendar)
+            .is_business_day(&date)
+        {
+            return Err(ContractError::NotABusinessDay(value_date.to_string()));
+        }
+
+        Ok(date)
+    }
+}
+
+pub(crate) fn parse_date(date: &str) -> Result<Date, ContractError> {
+    date.parse()
+        .map_err(|_| ContractError::InvalidDate(date.to_string()))
+}
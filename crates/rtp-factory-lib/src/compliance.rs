@@ -0,0 +1,34 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::near_bindgen;
+use rtp_contract_common::Role;
+
+#[near_bindgen]
+impl Contract {
+    /// Put a counterparty on the screening list by the hash of its name,
+    /// so that all trades with it are held back from matching.
+    #[handle_result]
+    pub fn add_screened_counterparty(
+        &mut self,
+        counterparty_id: String,
+    ) -> Result<(), ContractError> {
+        self.assert_role(Role::Compliance)?;
+        self.screening_list.insert(counterparty_id);
+
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_screened_counterparty(
+        &mut self,
+        counterparty_id: String,
+    ) -> Result<(), ContractError> {
+        self.assert_role(Role::Compliance)?;
+        self.screening_list.remove(&counterparty_id);
+
+        Ok(())
+    }
+
+    pub fn is_screened_counterparty(&self, counterparty_id: String) -> bool {
+        self.screening_list.contains(&counterparty_id)
+    }
+}
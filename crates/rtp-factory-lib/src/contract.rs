@@ -1,19 +1,25 @@
 use crate::{
-    rtp, ContractError, StorageKey, BANK_DEPOSIT_COVER_ADDITIONAL_BYTES, BANK_DEPOSIT_TO_COVER_GAS,
-    CREATE_CALL_GAS, ON_CREATE_CALL_GAS,
+    rtp, ContractError, StorageKey, BANK_ARCHIVE_BATCH_SIZE, BANK_DEPOSIT_COVER_ADDITIONAL_BYTES,
+    BANK_DEPOSIT_TO_COVER_GAS, CREATE_CALL_GAS, ON_CREATE_CALL_GAS,
 };
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    env, near_bindgen,
-    store::{Lazy, UnorderedSet},
-    AccountId, Balance, PanicOnDefault, Promise, PromiseError,
+    env,
+    json_types::U128,
+    near_bindgen,
+    store::{Lazy, TreeMap, UnorderedMap, UnorderedSet},
+    AccountId, Balance, PanicOnDefault, Promise, PromiseError, PromiseOrValue,
 };
 use rtp_contract_common::{
-    MatchingStatus, PaymentConfirmation, PaymentStatus, RtpEvent, TradeDetails,
+    get_bank_id, get_partnership_id, AdminProposal, BlockTime, CurrencyToken, Date,
+    DeskPermissions, Exposure, LifecycleRequest, MatchingStatus, MultisigConfig, NettingCycle,
+    OperatorKey, Partnership, PartnershipTrade, PauseScope, PaymentConfirmation, PaymentStatus,
+    PaymentWindow, Product, PvpEscrow, QueuedSettlement, Role, RtpEvent, Trade, TradeDetails,
+    TradeSubmission, TraderPermissions, MAX_TRADE_BATCH_SIZE, OPERATOR_KEY_METHODS,
 };
 use serde_json::json;
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, BTreeSet},
     hash::{Hash, Hasher},
 };
 
@@ -22,6 +28,58 @@ use std::{
 pub struct Contract {
     pub bank_ids: UnorderedSet<String>,
     pub contract_code: Lazy<Vec<u8>>,
+    /// Bank names by bank ID.
+    pub banks: UnorderedMap<String, String>,
+    pub partnerships: UnorderedMap<String, Partnership>,
+    /// Exposures by partnership ID and currency.
+    pub exposures: UnorderedMap<(String, String), Exposure>,
+    /// Trades by partnership ID and trade ID.
+    pub partnership_trades: UnorderedMap<(String, String), PartnershipTrade>,
+    pub netting_cycles: UnorderedMap<u64, NettingCycle>,
+    pub next_netting_cycle_id: u64,
+    /// Tokenized cash by currency.
+    pub currency_tokens: UnorderedMap<String, CurrencyToken>,
+    /// PvP escrows by partnership ID and trade ID.
+    pub pvp_escrows: UnorderedMap<(String, String), PvpEscrow>,
+    /// Withdrawable token balances by account ID and token ID.
+    pub token_balances: UnorderedMap<(AccountId, AccountId), Balance>,
+    /// Accounts, which fund the PvP payment legs of a bank, by bank ID.
+    pub settlement_accounts: UnorderedMap<String, AccountId>,
+    /// Liquidity for the settlement queue by bank ID and currency.
+    pub bank_liquidity: UnorderedMap<(String, String), Balance>,
+    /// Queued settlements by partnership ID and trade ID.
+    pub settlement_queue: UnorderedMap<(String, String), QueuedSettlement>,
+    /// Keys of all queued settlements by their queue order.
+    pub settlement_queue_order: TreeMap<(u8, u64), (String, String)>,
+    pub next_queue_sequence: u64,
+    pub roles: UnorderedSet<(Role, AccountId)>,
+    /// Pending lifecycle requests by partnership ID and trade ID.
+    pub lifecycle_requests: UnorderedMap<(String, String), LifecycleRequest>,
+    /// Holidays by financial centre.
+    pub holidays: UnorderedMap<String, BTreeSet<Date>>,
+    /// Operating windows for payments by currency.
+    pub payment_windows: UnorderedMap<String, PaymentWindow>,
+    /// Hashes of counterparty names, whose trades are held back from matching.
+    pub screening_list: UnorderedSet<String>,
+    /// IDs of banks, which cannot perform new trades.
+    pub suspended_banks: UnorderedSet<String>,
+    /// Trades of removed banks by bank ID and position in the archive.
+    pub bank_archives: UnorderedMap<(String, u64), Trade>,
+    /// Number of archived trades by bank ID.
+    pub bank_archive_lengths: UnorderedMap<String, u64>,
+    /// Number of trades, which have neither been settled nor released, by bank ID.
+    pub open_trades: UnorderedMap<String, u64>,
+    /// Scopes, in which trading and settlement are paused.
+    pub paused_scopes: UnorderedSet<PauseScope>,
+    /// Account, which is allowed to call critical functions, unless a multisig is configured.
+    pub owner: AccountId,
+    /// Proposed new owner, which has not accepted the ownership yet.
+    pub pending_owner: Option<AccountId>,
+    /// Admins, who approve critical functions instead of the owner.
+    pub multisig: Option<MultisigConfig>,
+    /// Proposals of critical functions by proposal ID.
+    pub admin_proposals: UnorderedMap<u64, AdminProposal>,
+    pub next_proposal_id: u64,
 }
 
 #[near_bindgen]
@@ -31,13 +89,42 @@ impl Contract {
         Self {
             bank_ids: UnorderedSet::new(StorageKey::BankIds),
             contract_code: Lazy::new(StorageKey::ContractCode, Vec::new()),
+            banks: UnorderedMap::new(StorageKey::Banks),
+            partnerships: UnorderedMap::new(StorageKey::Partnerships),
+            exposures: UnorderedMap::new(StorageKey::Exposures),
+            partnership_trades: UnorderedMap::new(StorageKey::PartnershipTrades),
+            netting_cycles: UnorderedMap::new(StorageKey::NettingCycles),
+            next_netting_cycle_id: 0,
+            currency_tokens: UnorderedMap::new(StorageKey::CurrencyTokens),
+            pvp_escrows: UnorderedMap::new(StorageKey::PvpEscrows),
+            token_balances: UnorderedMap::new(StorageKey::TokenBalances),
+            settlement_accounts: UnorderedMap::new(StorageKey::SettlementAccounts),
+            bank_liquidity: UnorderedMap::new(StorageKey::BankLiquidity),
+            settlement_queue: UnorderedMap::new(StorageKey::SettlementQueue),
+            settlement_queue_order: TreeMap::new(StorageKey::SettlementQueueOrder),
+            next_queue_sequence: 0,
+            roles: UnorderedSet::new(StorageKey::Roles),
+            lifecycle_requests: UnorderedMap::new(StorageKey::LifecycleRequests),
+            holidays: UnorderedMap::new(StorageKey::Holidays),
+            payment_windows: UnorderedMap::new(StorageKey::PaymentWindows),
+            screening_list: UnorderedSet::new(StorageKey::ScreeningList),
+            suspended_banks: UnorderedSet::new(StorageKey::SuspendedBanks),
+            bank_archives: UnorderedMap::new(StorageKey::BankArchives),
+            bank_archive_lengths: UnorderedMap::new(StorageKey::BankArchiveLengths),
+            open_trades: UnorderedMap::new(StorageKey::OpenTrades),
+            paused_scopes: UnorderedSet::new(StorageKey::PausedScopes),
+            owner: env::predecessor_account_id(),
+            pending_owner: None,
+            multisig: None,
+            admin_proposals: UnorderedMap::new(StorageKey::AdminProposals),
+            next_proposal_id: 0,
         }
     }
 
     /// Store contract from input.
-    #[private]
     #[handle_result]
     pub fn store_contract(&mut self) -> Result<(), ContractError> {
+        self.assert_critical()?;
         let input = env::input().ok_or(ContractError::NoInput)?;
         self.contract_code.set(input);
 
@@ -45,21 +132,261 @@ impl Contract {
     }
 
     /// Clear storage for testing.
-    #[private]
+    /// The owner, roles, admins and their proposals are kept.
     #[handle_result]
-    pub fn clear_storage(&mut self) {
-        self.bank_ids.clear();
-        self.contract_code.set(vec![]);
+    pub fn clear_storage(&mut self) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_clear_storage();
+
+        Ok(())
+    }
+
+    /// Allow or disallow a counterparty of a bank, whose counterparties are maintained by the factory.
+    #[handle_result]
+    pub fn set_counterparty_allowed(
+        &mut self,
+        bank_id: String,
+        counterparty: String,
+        allowed: bool,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .set_counterparty_allowed(counterparty, allowed))
+    }
+
+    /// Set the four-eyes approval threshold of a currency on behalf of a bank.
+    #[handle_result]
+    pub fn set_approval_threshold(
+        &mut self,
+        bank_id: String,
+        ccy: String,
+        threshold: Option<f64>,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .set_approval_threshold(ccy, threshold))
+    }
+
+    /// Authorise or deauthorise an approver of trades on behalf of a bank.
+    #[handle_result]
+    pub fn set_approver(
+        &mut self,
+        bank_id: String,
+        account_id: AccountId,
+        authorised: bool,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let bank_account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(bank_account_id)
+            .with_unused_gas_weight(1)
+            .set_approver(account_id, authorised))
+    }
+
+    /// Set the permissions of a trader on behalf of a bank.
+    #[handle_result]
+    pub fn set_trader_permissions(
+        &mut self,
+        bank_id: String,
+        trader_id: String,
+        permissions: Option<TraderPermissions>,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .set_trader_permissions(trader_id, permissions))
+    }
+
+    /// Set the permissions of a desk on behalf of a bank.
+    #[handle_result]
+    pub fn set_desk_permissions(
+        &mut self,
+        bank_id: String,
+        desk: String,
+        permissions: Option<DeskPermissions>,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .set_desk_permissions(desk, permissions))
+    }
+
+    /// Enable or disable the trader and desk permissions of a bank.
+    #[handle_result]
+    pub fn set_permissions_enforced(
+        &mut self,
+        bank_id: String,
+        enforced: bool,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .set_permissions_enforced(enforced))
+    }
+
+    /// Top up the storage balance of a bank with the attached deposit.
+    #[payable]
+    #[handle_result]
+    pub fn storage_deposit(&mut self, bank_id: String) -> Result<Promise, ContractError> {
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_attached_deposit(env::attached_deposit())
+            .with_unused_gas_weight(1)
+            .storage_deposit())
+    }
+
+    /// Withdraw available storage balance of a bank, whose admin is the factory, to the factory.
+    #[handle_result]
+    pub fn storage_withdraw(
+        &mut self,
+        bank_id: String,
+        amount: Option<U128>,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .storage_withdraw(amount))
     }
 
+    /// Replace the access key of the operator of a bank.
+    #[handle_result]
+    pub fn rotate_operator_key(
+        &mut self,
+        bank_id: String,
+        operator_key: OperatorKey,
+    ) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .rotate_operator_key(operator_key))
+    }
+
+    #[handle_result]
+    pub fn revoke_operator_key(&mut self, bank_id: String) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .revoke_operator_key())
+    }
+
+    /// Suspend a bank, which blocks new trades with it, or lift its suspension.
+    #[handle_result]
+    pub fn suspend_bank(&mut self, bank_id: String, suspended: bool) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_suspend_bank(bank_id, suspended)
+    }
+
+    /// Remove a suspended bank without unsettled trades.
+    /// Its trades are archived in the factory, before its account is deleted.
+    #[handle_result]
+    pub fn remove_bank(&mut self, bank_id: String) -> Result<Promise, ContractError> {
+        self.assert_critical()?;
+        self.archive_and_remove_bank(bank_id)
+    }
+
+    /// Archives the next batch of trades of the bank, until all are archived,
+    /// before its account is deleted. The removal is aborted, if any batch fails.
     #[private]
-    pub fn remove_bank(&mut self, bank_id: String) -> Promise {
+    pub fn on_archive_bank(
+        &mut self,
+        bank_id: String,
+        skip: u32,
+        #[callback_result] trades: Result<Vec<Trade>, PromiseError>,
+    ) -> PromiseOrValue<()> {
+        let Ok(trades) = trades else {
+            self.abort_bank_removal(bank_id, "Failed to archive the trades of the bank");
+            return PromiseOrValue::Value(());
+        };
+        let is_last_batch = trades.len() < BANK_ARCHIVE_BATCH_SIZE as usize;
+        let mut length = self.bank_archive_lengths[&bank_id];
+        for trade in trades {
+            self.bank_archives.insert((bank_id.clone(), length), trade);
+            length += 1;
+        }
+        self.bank_archive_lengths.insert(bank_id.clone(), length);
+        if !is_last_batch {
+            return PromiseOrValue::Promise(
+                self.archive_trades(bank_id, skip + BANK_ARCHIVE_BATCH_SIZE),
+            );
+        }
+
         let factory_account_id = env::current_account_id();
         let bank_account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
 
-        rtp::ext(bank_account_id)
-            .delete_account()
-            .then(Self::ext(env::current_account_id()).on_remove_bank(bank_id))
+        PromiseOrValue::Promise(
+            rtp::ext(bank_account_id)
+                .delete_account()
+                .then(Self::ext(factory_account_id).on_remove_bank(bank_id)),
+        )
     }
 
     #[private]
@@ -68,14 +395,35 @@ impl Contract {
         bank_id: String,
         #[callback_result] callback_res: Result<(), PromiseError>,
     ) {
-        callback_res.unwrap();
+        if callback_res.is_err() {
+            self.abort_bank_removal(bank_id, "Failed to delete the account of the bank");
+            return;
+        }
         self.bank_ids.remove(&bank_id);
+        self.banks.remove(&bank_id);
+        self.suspended_banks.remove(&bank_id);
+
+        let event = RtpEvent::RemoveBank {
+            archived_trades: self.bank_archive_lengths[&bank_id],
+            bank_id,
+        };
+        event.emit();
     }
 
-    #[private]
+    /// Deploy a new bank contract. Its counterparties are maintained by `admin`,
+    /// which defaults to the factory.
+    /// Any deposit above the storage cost is refunded, as well as the whole deposit,
+    /// if the bank contract cannot be created.
+    /// The optional `operator_key` is added as function call access key of the bank account.
     #[handle_result]
     #[payable]
-    pub fn create_bank(&mut self, bank: String) -> Result<(), ContractError> {
+    pub fn create_bank(
+        &mut self,
+        bank: String,
+        admin: Option<AccountId>,
+        operator_key: Option<OperatorKey>,
+    ) -> Result<(), ContractError> {
+        self.assert_owner()?;
         let attached_deposit = env::attached_deposit();
         let factory_account_id = env::current_account_id();
 
@@ -101,17 +449,34 @@ impl Contract {
         }
 
         let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+        let refund_to = env::predecessor_account_id();
+        let excess_deposit = attached_deposit - storage_cost;
+        if excess_deposit > 0 {
+            Promise::new(refund_to.clone()).transfer(excess_deposit);
+        }
 
         let promise_id = env::promise_batch_create(&account_id);
         env::promise_batch_action_create_account(promise_id);
-        env::promise_batch_action_transfer(promise_id, attached_deposit);
+        env::promise_batch_action_transfer(promise_id, storage_cost);
         env::promise_batch_action_deploy_contract(promise_id, code);
+        if let Some(operator_key) = &operator_key {
+            env::promise_batch_action_add_key_with_function_call(
+                promise_id,
+                &operator_key.public_key,
+                0,
+                operator_key.allowance.0,
+                &account_id,
+                OPERATOR_KEY_METHODS,
+            );
+        }
         env::promise_batch_action_function_call(
             promise_id,
             "new",
             json!({
                 "factory": factory_account_id,
                 "bank": bank,
+                "admin": admin,
+                "operator_key": operator_key.map(|operator_key| operator_key.public_key),
             })
             .to_string()
             .as_bytes(),
@@ -125,7 +490,9 @@ impl Contract {
             "on_create_bank",
             json!({
                 "bank": bank,
-                "bank_id": bank_id
+                "bank_id": bank_id,
+                "refund_to": refund_to,
+                "deposit": U128(storage_cost),
             })
             .to_string()
             .as_bytes(),
@@ -137,30 +504,44 @@ impl Contract {
         Ok(())
     }
 
+    /// Registers the bank or refunds its deposit, which is returned to the factory
+    /// by the failed creation, if the bank contract could not be created.
     #[private]
     pub fn on_create_bank(
         &mut self,
         bank: String,
         bank_id: String,
+        refund_to: AccountId,
+        deposit: U128,
         #[callback_result] callback_res: Result<(), PromiseError>,
     ) {
-        callback_res.unwrap();
+        if let Err(err) = callback_res {
+            Promise::new(refund_to).transfer(deposit.0);
+
+            let event = RtpEvent::BankCreationFailed {
+                bank,
+                bank_id,
+                reason: format!("Creating or initializing the bank account failed: {err:?}"),
+            };
+            event.emit();
+            return;
+        }
         self.bank_ids.insert(bank_id.clone());
+        self.banks.insert(bank_id.clone(), bank.clone());
 
         let event = RtpEvent::NewBank { bank, bank_id };
         event.emit();
     }
 
-    #[private]
+    /// Can also be called by the bank contract on behalf of its operator.
     #[handle_result]
     pub fn perform_trade(
         &mut self,
         bank_id: String,
         trade_details: TradeDetails,
     ) -> Result<Promise, ContractError> {
-        if !self.bank_ids.contains(&bank_id) {
-            return Err(ContractError::BankNotYetExists);
-        }
+        self.assert_factory_or_bank(&[&bank_id])?;
+        let screened = self.accept_trade(&bank_id, &trade_details)?;
 
         let factory_account_id = env::current_account_id();
         let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
@@ -168,10 +549,130 @@ impl Contract {
         Ok(rtp::ext(account_id)
             .with_unused_gas_weight(1)
             // .with_attached_deposit(100_000_000_000_000_000_000_000)
-            .perform_trade(trade_details))
+            .perform_trade(trade_details.clone(), screened)
+            .then(Self::ext(factory_account_id).on_perform_trade(bank_id, trade_details)))
+    }
+
+    /// The exposure of a trade is only recorded, once the bank contract has stored it.
+    #[private]
+    pub fn on_perform_trade(
+        &mut self,
+        bank_id: String,
+        trade_details: TradeDetails,
+        #[callback_unwrap] _stored: (),
+    ) {
+        self.record_trade(&bank_id, &trade_details);
     }
 
+    /// Submit a batch of at most `MAX_TRADE_BATCH_SIZE` trades of a bank in a single transaction.
+    /// Every trade is validated and stored on its own. Returns the outcome of every trade.
+    #[handle_result]
+    pub fn perform_trades(
+        &mut self,
+        bank_id: String,
+        trades: Vec<TradeDetails>,
+    ) -> Result<PromiseOrValue<Vec<TradeSubmission>>, ContractError> {
+        self.assert_factory_or_bank(&[&bank_id])?;
+        if trades.len() > MAX_TRADE_BATCH_SIZE as usize {
+            return Err(ContractError::TradeBatchTooLarge(MAX_TRADE_BATCH_SIZE));
+        }
+
+        let mut submissions = vec![];
+        let mut accepted_trades = vec![];
+        for trade_details in trades {
+            let error = match self.accept_trade(&bank_id, &trade_details) {
+                Ok(screened) => {
+                    accepted_trades.push((trade_details.clone(), screened));
+                    None
+                }
+                Err(err) => Some(err.to_string()),
+            };
+            submissions.push(TradeSubmission {
+                trade_id: trade_details.trade_id,
+                error,
+            });
+        }
+        if accepted_trades.is_empty() {
+            return Ok(PromiseOrValue::Value(submissions));
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        Ok(rtp::ext(account_id)
+            .with_unused_gas_weight(1)
+            .perform_trades(accepted_trades.clone())
+            .then(
+                Self::ext(factory_account_id).on_perform_trades(
+                    bank_id,
+                    submissions,
+                    accepted_trades
+                        .into_iter()
+                        .map(|(trade_details, _)| trade_details)
+                        .collect(),
+                ),
+            )
+            .into())
+    }
+
+    /// Merges the outcomes of the trades stored by the bank contract into the submissions.
+    /// Only the exposure of trades, which have been stored, is recorded.
     #[private]
+    pub fn on_perform_trades(
+        &mut self,
+        bank_id: String,
+        submissions: Vec<TradeSubmission>,
+        accepted_trades: Vec<TradeDetails>,
+        #[callback_result] stored: Result<Vec<TradeSubmission>, PromiseError>,
+    ) -> Vec<TradeSubmission> {
+        let mut stored = stored.map(Vec::into_iter);
+        let mut accepted_trades = accepted_trades.into_iter();
+        submissions
+            .into_iter()
+            .map(|submission| {
+                if submission.error.is_some() {
+                    return submission;
+                }
+                let trade_details = accepted_trades.next().unwrap();
+                let submission = match &mut stored {
+                    Ok(stored) => stored.next().unwrap_or(submission),
+                    Err(_) => TradeSubmission {
+                        error: Some("Bank contract failed to store the batch".to_string()),
+                        ..submission
+                    },
+                };
+                if submission.error.is_none() {
+                    self.record_trade(&bank_id, &trade_details);
+                }
+                submission
+            })
+            .collect()
+    }
+
+    /// Called by the bank contract, after an approver rejected a trade.
+    /// The trade can no longer be matched, so its exposure is released.
+    #[handle_result]
+    pub fn release_trade(
+        &mut self,
+        bank_id: String,
+        partnership_id: String,
+        trade_id: String,
+    ) -> Result<(), ContractError> {
+        self.assert_factory_or_bank(&[&bank_id])?;
+        let partnership = self
+            .partnerships
+            .get(&partnership_id)
+            .ok_or(ContractError::PartnershipNotYetExists)?;
+        if get_bank_id(&partnership.bank_a) != bank_id
+            && get_bank_id(&partnership.bank_b) != bank_id
+        {
+            return Err(ContractError::BankNotInPartnership);
+        }
+        self.release_exposure(&partnership_id, &trade_id);
+
+        Ok(())
+    }
+
     #[handle_result]
     pub fn set_matching_status(
         &mut self,
@@ -181,9 +682,13 @@ impl Contract {
         trade_id: String,
         matching_status: MatchingStatus,
     ) -> Result<Promise, ContractError> {
+        self.assert_role(Role::Matcher)?;
         if !self.bank_ids.contains(&bank_a_id) || !self.bank_ids.contains(&bank_b_id) {
             return Err(ContractError::BankNotYetExists);
         }
+        self.assert_not_paused(&[&bank_a_id, &bank_b_id], Some(&partnership_id))?;
+
+        let matching_status = self.match_exposure(&partnership_id, &trade_id, matching_status);
 
         let factory_account_id = env::current_account_id();
         let account_a_id: AccountId = format!("{bank_a_id}.{factory_account_id}").parse().unwrap();
@@ -221,6 +726,7 @@ impl Contract {
         let matching_status = if settlement_a.is_err() || settlement_b.is_err() {
             rtp::ext(account_a_id).set_matching_status(trade_id.clone(), MatchingStatus::Error);
             rtp::ext(account_b_id).set_matching_status(trade_id.clone(), MatchingStatus::Error);
+            self.release_exposure(&partnership_id, &trade_id);
             MatchingStatus::Error
         } else {
             matching_status
@@ -229,12 +735,14 @@ impl Contract {
             partnership_id,
             trade_id,
             matching_status,
+            block_time: BlockTime::now(),
         };
 
         event.emit();
     }
 
-    #[private]
+    /// Can also be called by the bank contract of the creditor on behalf of its operator,
+    /// once the payment has been received.
     #[handle_result]
     pub fn confirm_payment(
         &mut self,
@@ -242,8 +750,22 @@ impl Contract {
         debitor_id: String,
         trade_id: String,
     ) -> Result<Promise, ContractError> {
-        if !self.bank_ids.contains(&creditor_id) || !self.bank_ids.contains(&debitor_id) {
-            return Err(ContractError::BankNotYetExists);
+        self.assert_factory_or_bank(&[&creditor_id])?;
+        let creditor = self
+            .banks
+            .get(&creditor_id)
+            .ok_or(ContractError::BankNotYetExists)?;
+        let debitor = self
+            .banks
+            .get(&debitor_id)
+            .ok_or(ContractError::BankNotYetExists)?;
+        let partnership_id = get_partnership_id(creditor.clone(), debitor.clone());
+        self.assert_not_paused(&[&creditor_id, &debitor_id], Some(&partnership_id))?;
+        if let Some(trade) = self
+            .partnership_trades
+            .get(&(partnership_id.clone(), trade_id.clone()))
+        {
+            self.assert_payment_window(trade)?;
         }
 
         let factory_account_id = env::current_account_id();
@@ -268,7 +790,6 @@ impl Contract {
     #[private]
     pub fn on_confirm_payment(&mut self, #[callback_unwrap] _a: (), #[callback_unwrap] _b: ()) {}
 
-    #[private]
     #[handle_result]
     pub fn set_payment_status(
         &mut self,
@@ -278,9 +799,14 @@ impl Contract {
         trade_id: String,
         payment_status: PaymentStatus,
     ) -> Result<Promise, ContractError> {
+        self.assert_role(Role::Matcher)?;
         if !self.bank_ids.contains(&bank_a_id) || !self.bank_ids.contains(&bank_b_id) {
             return Err(ContractError::BankNotYetExists);
         }
+        self.assert_not_paused(&[&bank_a_id, &bank_b_id], Some(&partnership_id))?;
+        self.assert_payment_status_window(&partnership_id, &trade_id, &payment_status)?;
+
+        self.settle_exposure(&partnership_id, &trade_id, &payment_status);
 
         let factory_account_id = env::current_account_id();
         let account_a_id: AccountId = format!("{bank_a_id}.{factory_account_id}").parse().unwrap();
@@ -315,18 +841,222 @@ impl Contract {
         #[callback_result] settlement_a: Result<(), PromiseError>,
         #[callback_result] settlement_b: Result<(), PromiseError>,
     ) {
+        let payment_status = if settlement_a.is_err() || settlement_b.is_err() {
+            rtp::ext(account_a_id).set_payment_status(trade_id.clone(), PaymentStatus::Error);
+            rtp::ext(account_b_id).set_payment_status(trade_id.clone(), PaymentStatus::Error);
+            self.settle_exposure(&partnership_id, &trade_id, &PaymentStatus::Error);
+            PaymentStatus::Error
+        } else {
+            payment_status
+        };
         let event = RtpEvent::SetPaymentStatus {
             partnership_id,
-            trade_id: trade_id.clone(),
-            payment_status: if settlement_a.is_err() || settlement_b.is_err() {
-                rtp::ext(account_a_id).set_payment_status(trade_id.clone(), PaymentStatus::Error);
-                rtp::ext(account_b_id).set_payment_status(trade_id, PaymentStatus::Error);
-                PaymentStatus::Error
-            } else {
-                payment_status
-            },
+            trade_id,
+            payment_status,
+            block_time: BlockTime::now(),
         };
 
         event.emit();
     }
 }
+
+impl Contract {
+    /// Validates a new trade of a bank.
+    /// Returns, whether its counterparty is on the screening list.
+    fn accept_trade(
+        &self,
+        bank_id: &str,
+        trade_details: &TradeDetails,
+    ) -> Result<bool, ContractError> {
+        let bank = self
+            .banks
+            .get(bank_id)
+            .ok_or(ContractError::BankNotYetExists)?
+            .clone();
+        if bank == trade_details.counterparty {
+            return Err(ContractError::InvalidCounterparty);
+        }
+        if self.suspended_banks.contains(bank_id)
+            || self
+                .suspended_banks
+                .contains(&get_bank_id(&trade_details.counterparty))
+        {
+            return Err(ContractError::BankSuspended);
+        }
+        if !self
+            .bank_ids
+            .contains(&get_bank_id(&trade_details.counterparty))
+        {
+            return Err(ContractError::UnknownCounterparty);
+        }
+
+        let partnership_id = get_partnership_id(bank, trade_details.counterparty.clone());
+        self.assert_not_paused(
+            &[bank_id, &get_bank_id(&trade_details.counterparty)],
+            Some(&partnership_id),
+        )?;
+        let partnership = self
+            .partnerships
+            .get(&partnership_id)
+            .ok_or(ContractError::PartnershipNotYetExists)?;
+        if !partnership.is_active() {
+            return Err(ContractError::PartnershipNotActive);
+        }
+        if let Some(violation) = partnership.terms.violation(trade_details) {
+            return Err(violation.into());
+        }
+        self.validate_dates(trade_details)?;
+        if trade_details.product == Product::Ndf
+            && trade_details
+                .ndf_contract(bank_id, &get_bank_id(&trade_details.counterparty))
+                .is_none()
+        {
+            return Err(ContractError::InvalidNdfTerms);
+        }
+        if trade_details.product == Product::Swap && !trade_details.is_valid_swap_leg() {
+            return Err(ContractError::InvalidSwapLeg);
+        }
+        let screened = self
+            .screening_list
+            .contains(&get_bank_id(&trade_details.counterparty));
+
+        Ok(screened)
+    }
+
+    /// Records the exposure of a trade, which has been stored by the bank contract.
+    fn record_trade(&mut self, bank_id: &str, trade_details: &TradeDetails) {
+        let Some(bank) = self.banks.get(bank_id).cloned() else {
+            return;
+        };
+        let partnership_id = get_partnership_id(bank, trade_details.counterparty.clone());
+        self.submit_exposure(bank_id, &partnership_id, trade_details);
+    }
+
+    /// The caller needs to be the factory itself or the bank contract of one of given banks.
+    fn assert_factory_or_bank(&self, bank_ids: &[&str]) -> Result<(), ContractError> {
+        let predecessor_id = env::predecessor_account_id();
+        let factory_account_id = env::current_account_id();
+        if predecessor_id == factory_account_id
+            || bank_ids
+                .iter()
+                .any(|bank_id| predecessor_id.as_str() == format!("{bank_id}.{factory_account_id}"))
+        {
+            Ok(())
+        } else {
+            Err(ContractError::NotFactoryOrBank)
+        }
+    }
+
+    /// The caller needs to be the bank contract of given bank.
+    pub(crate) fn assert_bank(&self, bank_id: &str) -> Result<(), ContractError> {
+        let factory_account_id = env::current_account_id();
+        if env::predecessor_account_id().as_str() != format!("{bank_id}.{factory_account_id}") {
+            return Err(ContractError::NotBank);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn apply_clear_storage(&mut self) {
+        self.bank_ids.clear();
+        self.contract_code.set(vec![]);
+        self.banks.clear();
+        self.partnerships.clear();
+        self.exposures.clear();
+        self.partnership_trades.clear();
+        self.netting_cycles.clear();
+        self.currency_tokens.clear();
+        self.pvp_escrows.clear();
+        self.token_balances.clear();
+        self.settlement_accounts.clear();
+        self.bank_liquidity.clear();
+        self.settlement_queue.clear();
+        self.settlement_queue_order.clear();
+        self.lifecycle_requests.clear();
+        self.holidays.clear();
+        self.payment_windows.clear();
+        self.screening_list.clear();
+        self.suspended_banks.clear();
+        self.bank_archives.clear();
+        self.bank_archive_lengths.clear();
+        self.open_trades.clear();
+        self.paused_scopes.clear();
+    }
+
+    pub(crate) fn apply_suspend_bank(
+        &mut self,
+        bank_id: String,
+        suspended: bool,
+    ) -> Result<(), ContractError> {
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        if suspended {
+            self.suspended_banks.insert(bank_id.clone());
+        } else {
+            self.suspended_banks.remove(&bank_id);
+        }
+
+        let event = RtpEvent::SuspendBank { bank_id, suspended };
+        event.emit();
+
+        Ok(())
+    }
+
+    /// Checks, whether a bank can be removed, and hands its trades off to the factory,
+    /// before its account is deleted.
+    pub(crate) fn archive_and_remove_bank(
+        &mut self,
+        bank_id: String,
+    ) -> Result<Promise, ContractError> {
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+        if !self.suspended_banks.contains(&bank_id) {
+            return Err(ContractError::BankNotSuspended);
+        }
+        if self.has_open_trades(&bank_id) {
+            return Err(ContractError::BankHasOpenTrades);
+        }
+
+        self.bank_archive_lengths.insert(bank_id.clone(), 0);
+
+        Ok(self.archive_trades(bank_id, 0))
+    }
+
+    /// Hands off the batch of trades of a bank, which starts at `skip`, to the factory.
+    fn archive_trades(&self, bank_id: String, skip: u32) -> Promise {
+        let factory_account_id = env::current_account_id();
+        let bank_account_id = format!("{bank_id}.{factory_account_id}").parse().unwrap();
+
+        rtp::ext(bank_account_id)
+            .get_trades(Some(skip), Some(BANK_ARCHIVE_BATCH_SIZE))
+            .then(Self::ext(factory_account_id).on_archive_bank(bank_id, skip))
+    }
+
+    /// Discards the partial archive of a bank, which remains in place.
+    fn abort_bank_removal(&mut self, bank_id: String, reason: &str) {
+        let length = self
+            .bank_archive_lengths
+            .remove(&bank_id)
+            .unwrap_or_default();
+        for position in 0..length {
+            self.bank_archives.remove(&(bank_id.clone(), position));
+        }
+
+        let event = RtpEvent::BankRemovalFailed {
+            bank_id,
+            reason: reason.to_string(),
+        };
+        event.emit();
+    }
+
+    /// Whether any trade in a partnership of the bank has neither been settled nor released.
+    fn has_open_trades(&self, bank_id: &str) -> bool {
+        self.open_trades
+            .get(bank_id)
+            .map(|count| *count > 0)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId};
+use rtp_contract_common::{
+    Date, PartnershipTrade, PartnershipTradeStatus, PaymentStatus, PaymentWindow, RtpEvent,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Set the daily operating window of a currency.
+    /// Payments in currencies without a window can be made at any time.
+    #[handle_result]
+    pub fn set_payment_window(
+        &mut self,
+        ccy: String,
+        window: Option<PaymentWindow>,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_payment_window(ccy, window)
+    }
+
+    pub fn get_payment_window(&self, ccy: String) -> Option<PaymentWindow> {
+        self.payment_windows.get(&ccy).cloned()
+    }
+
+    /// Mark those of the partnership trades from `skip` to `skip + limit`, which are matched and unsettled
+    /// and whose cut-off has passed, as missed and remove them from the settlement queue.
+    /// Trades, which missed their cut-off, can be rolled or terminated via `request_lifecycle`.
+    pub fn check_cut_offs(&mut self, skip: Option<u32>, limit: Option<u32>) {
+        let timestamp_ms = env::block_timestamp_ms();
+        let missed: Vec<_> = self
+            .partnership_trades
+            .iter()
+            .skip(skip.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(20) as usize)
+            .filter(|(trade_key, trade)| {
+                trade.status == PartnershipTradeStatus::Matched
+                    && trade.netting_cycle.is_none()
+                    && !self.pvp_escrows.contains_key(*trade_key)
+            })
+            .filter_map(|(trade_key, trade)| {
+                let ccy = self.missed_cut_off(trade, timestamp_ms)?;
+                Some((trade_key.clone(), trade.clone(), ccy))
+            })
+            .collect();
+
+        let factory_account_id = env::current_account_id();
+        for ((partnership_id, trade_id), trade, ccy) in missed {
+            let trade_key = (partnership_id.clone(), trade_id.clone());
+            if self.settlement_queue.contains_key(&trade_key) {
+                self.remove_queued_settlement(partnership_id.clone(), trade_id.clone())
+                    .unwrap();
+            }
+            self.set_partnership_trade_status(&trade_key, PartnershipTradeStatus::MissedCutOff);
+
+            let event = RtpEvent::MissCutOff {
+                partnership_id,
+                trade_id: trade_id.clone(),
+                ccy: ccy.clone(),
+                value_date: trade.value_date.clone(),
+            };
+            event.emit();
+
+            let payment_status = PaymentStatus::MissedCutOff(format!(
+                "{ccy} cut-off missed for value date {}",
+                trade.value_date
+            ));
+            let leg = &trade.legs[0];
+            for bank_id in [&leg.payer, &leg.receiver] {
+                let account_id: AccountId =
+                    format!("{bank_id}.{factory_account_id}").parse().unwrap();
+                rtp::ext(account_id).set_payment_status(trade_id.clone(), payment_status.clone());
+            }
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn apply_payment_window(
+        &mut self,
+        ccy: String,
+        window: Option<PaymentWindow>,
+    ) -> Result<(), ContractError> {
+        if let Some(window) = window {
+            if !window.is_valid() {
+                return Err(ContractError::InvalidPaymentWindow);
+            }
+            self.payment_windows.insert(ccy, window);
+        } else {
+            self.payment_windows.remove(&ccy);
+        }
+
+        Ok(())
+    }
+
+    /// Payments of a trade can only be made within the operating windows of all its currencies
+    /// and before the cut-off on its value date.
+    pub(crate) fn assert_payment_window(
+        &self,
+        trade: &PartnershipTrade,
+    ) -> Result<(), ContractError> {
+        let timestamp_ms = env::block_timestamp_ms();
+        if let Some(ccy) = self.missed_cut_off(trade, timestamp_ms) {
+            return Err(ContractError::CutOffMissed(ccy));
+        }
+        if let Some(ccy) = trade.legs.iter().map(|leg| &leg.ccy).find(|ccy| {
+            self.payment_windows
+                .get(*ccy)
+                .map(|window| !window.is_open(timestamp_ms))
+                .unwrap_or_default()
+        }) {
+            return Err(ContractError::OutsidePaymentWindow(ccy.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Only confirmations of a payment have to be within the payment window of the trade.
+    pub(crate) fn assert_payment_status_window(
+        &self,
+        partnership_id: &str,
+        trade_id: &str,
+        payment_status: &PaymentStatus,
+    ) -> Result<(), ContractError> {
+        if !matches!(payment_status, PaymentStatus::Confirmed(_)) {
+            return Ok(());
+        }
+        match self
+            .partnership_trades
+            .get(&(partnership_id.to_string(), trade_id.to_string()))
+        {
+            Some(trade) => self.assert_payment_window(trade),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether all currencies of a trade are within their operating windows.
+    pub(crate) fn is_payment_window_open(&self, trade: &PartnershipTrade) -> bool {
+        self.assert_payment_window(trade).is_ok()
+    }
+
+    /// Currency of a trade, whose cut-off on the value date of the trade has passed.
+    fn missed_cut_off(&self, trade: &PartnershipTrade, timestamp_ms: u64) -> Option<String> {
+        let value_date: Date = trade.value_date.parse().ok()?;
+        trade
+            .legs
+            .iter()
+            .find(|leg| {
+                self.payment_windows
+                    .get(&leg.ccy)
+                    .map(|window| window.is_missed(&value_date, timestamp_ms))
+                    .unwrap_or_default()
+            })
+            .map(|leg| leg.ccy.clone())
+    }
+}
@@ -2,18 +2,155 @@ use near_sdk::{
     borsh::{self, BorshSerialize},
     Balance, FunctionError,
 };
+use rtp_contract_common::{PauseScope, Product, Role, TermsViolation};
 use thiserror::Error;
 
 #[derive(BorshSerialize, Debug, Error, FunctionError)]
 pub enum ContractError {
     #[error("No input")]
     NoInput,
+    #[error("Migration failed: {_0}")]
+    MigrationFailed(String),
     #[error("Not enough deposit. Required: {_0}; actual: {_1}")]
     NotEnoughDeposit(Balance, Balance),
     #[error("Bank contract already exists")]
     BankAlreadyExists,
     #[error("Bank contract does not yet exists")]
     BankNotYetExists,
+    #[error("Bank is suspended")]
+    BankSuspended,
+    #[error("Bank needs to be suspended before it can be removed")]
+    BankNotSuspended,
+    #[error("Bank has trades, which are not yet settled")]
+    BankHasOpenTrades,
+    #[error("Trading is paused in scope: {_0}")]
+    Paused(PauseScope),
+    #[error("Pause scope refers to an unknown bank or partnership")]
+    InvalidPauseScope,
+    #[error("Only the owner can call this function")]
+    NotOwner,
+    #[error("Only the proposed owner can accept the ownership")]
+    NotPendingOwner,
+    #[error("Function requires the approval of the multisig admins")]
+    MultisigRequired,
+    #[error("No multisig configured")]
+    MultisigNotEnabled,
+    #[error("Only multisig admins can call this function")]
+    NotMultisigAdmin,
+    #[error("Multisig threshold must be between 1 and the number of admins")]
+    InvalidMultisigConfig,
+    #[error("Proposal does not exist")]
+    InvalidProposalId,
+    #[error("Proposal has expired")]
+    ProposalExpired,
+    #[error("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[error("Proposal has already been approved by this admin")]
+    AlreadyApproved,
+    #[error("Only the factory or the bank contract can call this function")]
+    NotFactoryOrBank,
+    #[error("Only the bank contract can call this function")]
+    NotBank,
+    #[error("Too many trades in batch. Maximum: {_0}")]
+    TradeBatchTooLarge(u32),
+    #[error("Too many status updates in batch. Maximum: {_0}")]
+    StatusBatchTooLarge(u32),
     #[error("Invalid bank input")]
     InvalidBankInput,
+    #[error("Bank cannot trade with itself")]
+    InvalidCounterparty,
+    #[error("Counterparty is not a registered bank")]
+    UnknownCounterparty,
+    #[error("Partnership already exists")]
+    PartnershipAlreadyExists,
+    #[error("Partnership does not yet exists")]
+    PartnershipNotYetExists,
+    #[error("Partnership is not active")]
+    PartnershipNotActive,
+    #[error("Partnership has not been proposed by counterparty")]
+    PartnershipNotProposedByCounterparty,
+    #[error("Product {_0:?} not covered by partnership terms")]
+    ProductNotCovered(Product),
+    #[error("Currency {_0} not covered by partnership terms")]
+    CurrencyNotCovered(String),
+    #[error("Settlement method {_0} not covered by partnership terms")]
+    SettlementMethodNotCovered(String),
+    #[error("Invalid exposure limit")]
+    InvalidLimit,
+    #[error("No matched and unsettled trades to net")]
+    NothingToNet,
+    #[error("Netting cycle does not exist")]
+    InvalidNettingCycle,
+    #[error("Netting cycle is not open")]
+    NettingCycleNotOpen,
+    #[error("Netting cycle has not failed")]
+    NettingCycleNotFailed,
+    #[error("Bank is neither payer nor receiver of the net payment")]
+    BankNotInNetPayment,
+    #[error("Settlement instruction does not exist")]
+    InvalidSettlementInstruction,
+    #[error("Net payment already confirmed")]
+    NetPaymentAlreadyConfirmed,
+    #[error("Invalid token transfer message")]
+    InvalidFtTransferMessage,
+    #[error("Currency is not tokenized")]
+    CurrencyNotTokenized,
+    #[error("Trade does not exist")]
+    InvalidTradeId,
+    #[error("Trade is not matched or already settled")]
+    TradeNotSettleable,
+    #[error("PvP settlement requires exactly two payment legs")]
+    PvpNotSupported,
+    #[error("No payment leg for given bank and currency")]
+    InvalidPvpLeg,
+    #[error("Payment leg has already been funded by another account")]
+    InvalidPvpDepositor,
+    #[error("Only the settlement account of the paying bank can fund its payment leg")]
+    NotSettlementAccount,
+    #[error("Not enough balance. Requested: {_0}; available: {_1}")]
+    NotEnoughBalance(Balance, Balance),
+    #[error("Trade is not queued for settlement")]
+    SettlementNotQueued,
+    #[error("Missing role {_0:?}")]
+    MissingRole(Role),
+    #[error("NDF requires fixing date, fixing source and a settlement currency of the instrument")]
+    InvalidNdfTerms,
+    #[error("Trade is not an NDF")]
+    NotAnNdf,
+    #[error("Invalid fixing rate")]
+    InvalidFixingRate,
+    #[error("NDF has already been fixed")]
+    NdfAlreadyFixed,
+    #[error("Fixing date {0} has not been reached yet")]
+    FixingDateNotReached(String),
+    #[error("Swap leg requires its leg type and the trade ID of the linked leg")]
+    InvalidSwapLeg,
+    #[error("Bank is not part of the partnership")]
+    BankNotInPartnership,
+    #[error("Trade is not a forward")]
+    NotAForward,
+    #[error("Invalid lifecycle action")]
+    InvalidLifecycleAction,
+    #[error("Invalid date {_0}, expected DD.MM.YYYY")]
+    InvalidDate(String),
+    #[error("{_0} is not a business day")]
+    NotABusinessDay(String),
+    #[error("Invalid payment window")]
+    InvalidPaymentWindow,
+    #[error("Cut-off for {_0} payments has been missed")]
+    CutOffMissed(String),
+    #[error("Payments in {_0} are outside of their operating window")]
+    OutsidePaymentWindow(String),
+}
+
+impl From<TermsViolation> for ContractError {
+    fn from(violation: TermsViolation) -> Self {
+        match violation {
+            TermsViolation::Product(product) => ContractError::ProductNotCovered(product),
+            TermsViolation::Currency(ccy) => ContractError::CurrencyNotCovered(ccy),
+            TermsViolation::SettlementMethod(method) => {
+                ContractError::SettlementMethodNotCovered(method)
+            }
+        }
+    }
 }
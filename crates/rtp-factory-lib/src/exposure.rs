@@ -0,0 +1,269 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::near_bindgen;
+use rtp_contract_common::{
+    get_bank_id, Exposure, MatchingStatus, PartnershipTrade, PartnershipTradeStatus, PaymentStatus,
+    RtpEvent, TradeDetails,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Set the exposure limit of a partnership for given currency.
+    /// Removes the limit, if `limit` is not set.
+    #[handle_result]
+    pub fn set_exposure_limit(
+        &mut self,
+        partnership_id: String,
+        ccy: String,
+        limit: Option<f64>,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_exposure_limit(partnership_id, ccy, limit)
+    }
+}
+
+impl Contract {
+    pub(crate) fn apply_exposure_limit(
+        &mut self,
+        partnership_id: String,
+        ccy: String,
+        limit: Option<f64>,
+    ) -> Result<(), ContractError> {
+        if !self.partnerships.contains_key(&partnership_id) {
+            return Err(ContractError::PartnershipNotYetExists);
+        }
+        if let Some(limit) = limit {
+            if !limit.is_finite() || limit < 0. {
+                return Err(ContractError::InvalidLimit);
+            }
+        }
+
+        let key = (partnership_id, ccy);
+        if let Some(exposure) = self.exposures.get_mut(&key) {
+            exposure.limit = limit;
+        } else {
+            self.exposures.insert(
+                key,
+                Exposure {
+                    limit,
+                    ..Default::default()
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Track a newly submitted trade. Only the first submission of a trade counts towards exposure,
+    /// because both banks submit the same trade.
+    pub(crate) fn submit_exposure(
+        &mut self,
+        bank_id: &str,
+        partnership_id: &str,
+        trade_details: &TradeDetails,
+    ) {
+        let trade_key = (partnership_id.to_string(), trade_details.trade_id.clone());
+        if self.partnership_trades.contains_key(&trade_key) {
+            return;
+        }
+
+        let amount = trade_details.notional_amount as f64;
+        self.insert_partnership_trade(
+            trade_key,
+            PartnershipTrade {
+                product: trade_details.product.clone(),
+                ccy: trade_details.ccy.clone(),
+                amount,
+                value_date: trade_details.ccy1_value_date.clone(),
+                legs: trade_details
+                    .payment_legs(bank_id, &get_bank_id(&trade_details.counterparty)),
+                status: PartnershipTradeStatus::Submitted,
+                netting_cycle: None,
+                ndf: trade_details.ndf_contract(bank_id, &get_bank_id(&trade_details.counterparty)),
+                payment_calendar: trade_details.payment_calendar.clone(),
+            },
+        );
+        self.exposure_mut(partnership_id, &trade_details.ccy)
+            .submitted += amount;
+    }
+
+    /// Apply a new matching status to the partnership exposure.
+    /// Confirmations, that would breach the exposure limit, are turned into rejections.
+    pub(crate) fn match_exposure(
+        &mut self,
+        partnership_id: &str,
+        trade_id: &str,
+        matching_status: MatchingStatus,
+    ) -> MatchingStatus {
+        let trade_key = (partnership_id.to_string(), trade_id.to_string());
+        let Some(trade) = self.partnership_trades.get(&trade_key).cloned() else {
+            return matching_status;
+        };
+        if trade.status != PartnershipTradeStatus::Submitted {
+            return matching_status;
+        }
+
+        let exposure = self.exposure_mut(partnership_id, &trade.ccy);
+        let (matching_status, status) = match matching_status {
+            MatchingStatus::Confirmed(_) if exposure.would_breach(trade.amount) => {
+                let event = RtpEvent::LimitBreach {
+                    partnership_id: partnership_id.to_string(),
+                    trade_id: trade_id.to_string(),
+                    ccy: trade.ccy.clone(),
+                    limit: exposure.limit.unwrap_or_default(),
+                    exposure: exposure.matched,
+                    amount: trade.amount,
+                };
+                event.emit();
+
+                exposure.submitted -= trade.amount;
+                (
+                    MatchingStatus::Rejected("Exposure limit breached".to_string()),
+                    PartnershipTradeStatus::Released,
+                )
+            }
+            MatchingStatus::Confirmed(message) => {
+                exposure.submitted -= trade.amount;
+                exposure.matched += trade.amount;
+                (
+                    MatchingStatus::Confirmed(message),
+                    PartnershipTradeStatus::Matched,
+                )
+            }
+            MatchingStatus::Pending => return MatchingStatus::Pending,
+            matching_status => {
+                exposure.submitted -= trade.amount;
+                (matching_status, PartnershipTradeStatus::Released)
+            }
+        };
+        self.set_partnership_trade_status(&trade_key, status);
+
+        matching_status
+    }
+
+    /// Release the exposure of a trade, which failed to be matched.
+    pub(crate) fn release_exposure(&mut self, partnership_id: &str, trade_id: &str) {
+        let trade_key = (partnership_id.to_string(), trade_id.to_string());
+        let Some(trade) = self.partnership_trades.get(&trade_key).cloned() else {
+            return;
+        };
+
+        let exposure = self.exposure_mut(partnership_id, &trade.ccy);
+        match trade.status {
+            PartnershipTradeStatus::Submitted => exposure.submitted -= trade.amount,
+            PartnershipTradeStatus::Matched | PartnershipTradeStatus::MissedCutOff => {
+                exposure.matched -= trade.amount
+            }
+            PartnershipTradeStatus::Settled | PartnershipTradeStatus::Released => return,
+        }
+        self.set_partnership_trade_status(&trade_key, PartnershipTradeStatus::Released);
+    }
+
+    /// Track a released trade as matched again, possibly with a missed cut-off.
+    pub(crate) fn restore_exposure(
+        &mut self,
+        partnership_id: &str,
+        trade_id: &str,
+        status: PartnershipTradeStatus,
+    ) {
+        let trade_key = (partnership_id.to_string(), trade_id.to_string());
+        let Some(trade) = self.partnership_trades.get(&trade_key).cloned() else {
+            return;
+        };
+        if trade.status != PartnershipTradeStatus::Released {
+            return;
+        }
+
+        self.exposure_mut(partnership_id, &trade.ccy).matched += trade.amount;
+        self.set_partnership_trade_status(&trade_key, status);
+    }
+
+    /// Apply a new payment status to the partnership exposure.
+    pub(crate) fn settle_exposure(
+        &mut self,
+        partnership_id: &str,
+        trade_id: &str,
+        payment_status: &PaymentStatus,
+    ) {
+        let trade_key = (partnership_id.to_string(), trade_id.to_string());
+        let Some(trade) = self.partnership_trades.get(&trade_key).cloned() else {
+            return;
+        };
+
+        let exposure = self.exposure_mut(partnership_id, &trade.ccy);
+        let status = match (&trade.status, payment_status) {
+            (
+                PartnershipTradeStatus::Matched | PartnershipTradeStatus::MissedCutOff,
+                PaymentStatus::Confirmed(_),
+            ) => {
+                exposure.matched -= trade.amount;
+                PartnershipTradeStatus::Settled
+            }
+            (PartnershipTradeStatus::Settled, PaymentStatus::Error) => {
+                exposure.matched += trade.amount;
+                PartnershipTradeStatus::Matched
+            }
+            _ => return,
+        };
+        self.set_partnership_trade_status(&trade_key, status);
+    }
+
+    pub(crate) fn exposure_mut(&mut self, partnership_id: &str, ccy: &str) -> &mut Exposure {
+        let key = (partnership_id.to_string(), ccy.to_string());
+        if !self.exposures.contains_key(&key) {
+            self.exposures.insert(key.clone(), Exposure::default());
+        }
+        self.exposures.get_mut(&key).unwrap()
+    }
+
+    /// Track a new partnership trade, which counts as open for both banks of the partnership.
+    pub(crate) fn insert_partnership_trade(
+        &mut self,
+        trade_key: (String, String),
+        trade: PartnershipTrade,
+    ) {
+        if trade.is_open() {
+            self.count_open_trade(&trade_key.0, true);
+        }
+        self.partnership_trades.insert(trade_key, trade);
+    }
+
+    pub(crate) fn set_partnership_trade_status(
+        &mut self,
+        trade_key: &(String, String),
+        status: PartnershipTradeStatus,
+    ) {
+        let Some(trade) = self.partnership_trades.get_mut(trade_key) else {
+            return;
+        };
+        let was_open = trade.is_open();
+        trade.status = status;
+        let is_open = trade.is_open();
+        if was_open != is_open {
+            self.count_open_trade(&trade_key.0, is_open);
+        }
+    }
+
+    /// Count a trade of the partnership as opened or closed for both of its banks.
+    fn count_open_trade(&mut self, partnership_id: &str, opened: bool) {
+        let Some(partnership) = self.partnerships.get(partnership_id) else {
+            return;
+        };
+        let bank_ids = [
+            get_bank_id(&partnership.bank_a),
+            get_bank_id(&partnership.bank_b),
+        ];
+        for bank_id in bank_ids {
+            let count = self.open_trades.get(&bank_id).copied().unwrap_or_default();
+            let count = if opened {
+                count + 1
+            } else {
+                count.saturating_sub(1)
+            };
+            if count == 0 {
+                self.open_trades.remove(&bank_id);
+            } else {
+                self.open_trades.insert(bank_id, count);
+            }
+        }
+    }
+}
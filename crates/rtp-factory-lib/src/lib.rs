@@ -1,20 +1,64 @@
+mod batch;
+mod calendar;
+mod compliance;
 mod contract;
+mod cutoff;
 mod error;
+mod exposure;
+mod lifecycle;
+mod migration;
+mod ndf;
+mod netting;
+mod owner;
+mod partnership;
+mod pause;
+mod pvp;
+mod queue;
+mod role;
 mod view;
 
 pub use contract::*;
 pub use error::*;
+pub use pvp::*;
 
 use near_sdk::{
     borsh::{self, BorshSerialize},
-    ext_contract, Balance, BorshStorageKey, Gas, ONE_NEAR,
+    ext_contract,
+    json_types::U128,
+    AccountId, Balance, BorshStorageKey, Gas, ONE_NEAR,
+};
+use rtp_contract_common::{
+    DeskPermissions, LifecycleAction, MatchingStatus, NdfFixing, OperatorKey, PaymentConfirmation,
+    PaymentStatus, Trade, TradeDetails, TraderPermissions,
 };
-use rtp_contract_common::{MatchingStatus, PaymentConfirmation, PaymentStatus, TradeDetails};
 
 #[derive(BorshStorageKey, BorshSerialize)]
 pub enum StorageKey {
     BankIds,
     ContractCode,
+    Banks,
+    Partnerships,
+    Exposures,
+    PartnershipTrades,
+    NettingCycles,
+    CurrencyTokens,
+    PvpEscrows,
+    TokenBalances,
+    BankLiquidity,
+    SettlementQueue,
+    Roles,
+    LifecycleRequests,
+    Holidays,
+    PaymentWindows,
+    ScreeningList,
+    SuspendedBanks,
+    BankArchives,
+    PausedScopes,
+    AdminProposals,
+    SettlementAccounts,
+    SettlementQueueOrder,
+    BankArchiveLengths,
+    OpenTrades,
 }
 
 /// Gas spent on the call & account creation.
@@ -23,19 +67,66 @@ const CREATE_CALL_GAS: Gas = Gas::from_tgas(40);
 /// Gas allocated on the callback.
 const ON_CREATE_CALL_GAS: Gas = Gas::from_tgas(10);
 
+/// Gas allocated on NEP-141 token transfers.
+const FT_TRANSFER_GAS: Gas = Gas::from_tgas(10);
+
 const BANK_DEPOSIT_COVER_ADDITIONAL_BYTES: usize = 1024;
 
 const BANK_DEPOSIT_TO_COVER_GAS: Balance = 2 * ONE_NEAR;
 
+/// Number of trades, which are archived per call, when a bank is removed.
+const BANK_ARCHIVE_BATCH_SIZE: u32 = 50;
+
 #[ext_contract(rtp)]
 trait Rtp {
-    fn perform_trade(&mut self, trade_details: TradeDetails);
+    fn perform_trade(&mut self, trade_details: TradeDetails, screened: bool);
+
+    fn perform_trades(&mut self, trades: Vec<(TradeDetails, bool)>);
 
     fn set_matching_status(&mut self, trade_id: String, matching_status: MatchingStatus);
 
+    fn set_matching_statuses(&mut self, updates: Vec<(String, MatchingStatus)>);
+
     fn confirm_payment(&mut self, trade_id: String, confirmation: PaymentConfirmation);
 
     fn set_payment_status(&mut self, trade_id: String, payment_status: PaymentStatus);
 
+    fn set_payment_statuses(&mut self, updates: Vec<(String, PaymentStatus)>);
+
+    fn settle_trades(&mut self, trade_ids: Vec<String>, message: String);
+
+    fn set_ndf_fixing(&mut self, trade_id: String, fixing: NdfFixing);
+
+    fn apply_lifecycle(&mut self, trade_id: String, action: LifecycleAction);
+
+    fn set_counterparty_allowed(&mut self, counterparty: String, allowed: bool);
+
+    fn set_approval_threshold(&mut self, ccy: String, threshold: Option<f64>);
+
+    fn set_approver(&mut self, account_id: AccountId, authorised: bool);
+
+    fn set_trader_permissions(&mut self, trader_id: String, permissions: Option<TraderPermissions>);
+
+    fn set_desk_permissions(&mut self, desk: String, permissions: Option<DeskPermissions>);
+
+    fn set_permissions_enforced(&mut self, enforced: bool);
+
+    fn storage_deposit(&mut self);
+
+    fn storage_withdraw(&mut self, amount: Option<U128>);
+
+    fn get_trades(&self, skip: Option<u32>, limit: Option<u32>) -> Vec<Trade>;
+
+    fn set_paused(&mut self, paused: bool);
+
+    fn rotate_operator_key(&mut self, operator_key: OperatorKey);
+
+    fn revoke_operator_key(&mut self);
+
     fn delete_account(&mut self);
 }
+
+#[ext_contract(ft)]
+trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
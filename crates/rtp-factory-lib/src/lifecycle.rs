@@ -0,0 +1,232 @@
+use crate::{rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId, PromiseError};
+use rtp_contract_common::{
+    close_out_trade_id, get_bank_id, Date, LifecycleAction, LifecycleRequest, PartnershipTrade,
+    PartnershipTradeStatus, Product, RtpEvent,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Request to roll or terminate a matched forward or any trade, which missed its cut-off,
+    /// on behalf of one of its counterparties.
+    /// The operation is applied, as soon as the other counterparty requests the same operation.
+    /// A differing request replaces the pending one.
+    /// The new value date needs to be a business day of the trade's payment calendar
+    /// and, when rolling, later than the original value date.
+    /// Can only be called by the bank contract on behalf of its admin.
+    #[handle_result]
+    pub fn request_lifecycle(
+        &mut self,
+        bank_id: String,
+        partnership_id: String,
+        trade_id: String,
+        action: LifecycleAction,
+    ) -> Result<(), ContractError> {
+        self.assert_bank(&bank_id)?;
+        let partnership = self
+            .partnerships
+            .get(&partnership_id)
+            .ok_or(ContractError::PartnershipNotYetExists)?;
+        let bank_ids = [
+            get_bank_id(&partnership.bank_a),
+            get_bank_id(&partnership.bank_b),
+        ];
+        if !bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotInPartnership);
+        }
+        self.assert_not_paused(&[&bank_ids[0], &bank_ids[1]], Some(&partnership_id))?;
+
+        let trade_key = (partnership_id.clone(), trade_id.clone());
+        let trade = self
+            .partnership_trades
+            .get(&trade_key)
+            .ok_or(ContractError::InvalidTradeId)?;
+        let is_missed = trade.status == PartnershipTradeStatus::MissedCutOff;
+        if trade.product != Product::Fwd && !is_missed {
+            return Err(ContractError::NotAForward);
+        }
+        if !(trade.status == PartnershipTradeStatus::Matched || is_missed)
+            || trade.netting_cycle.is_some()
+            || self.pvp_escrows.contains_key(&trade_key)
+            || self.settlement_queue.contains_key(&trade_key)
+        {
+            return Err(ContractError::TradeNotSettleable);
+        }
+        let is_valid = match &action {
+            LifecycleAction::Roll {
+                new_trade_id,
+                value_date,
+                price,
+            } => {
+                let value_date = self.validate_value_date(&trade.payment_calendar, value_date)?;
+                !self
+                    .partnership_trades
+                    .contains_key(&(partnership_id.clone(), new_trade_id.clone()))
+                    && price.is_finite()
+                    && *price > 0.
+                    && trade
+                        .value_date
+                        .parse::<Date>()
+                        .map(|original| value_date > original)
+                        .unwrap_or_default()
+            }
+            LifecycleAction::Terminate {
+                close_out,
+                value_date,
+            } => {
+                self.validate_value_date(&trade.payment_calendar, value_date)?;
+                !self
+                    .partnership_trades
+                    .contains_key(&(partnership_id.clone(), close_out_trade_id(&trade_id)))
+                    && close_out.payer != close_out.receiver
+                    && bank_ids.contains(&close_out.payer)
+                    && bank_ids.contains(&close_out.receiver)
+                    && close_out.amount.is_finite()
+                    && close_out.amount >= 0.
+            }
+        };
+        if !is_valid {
+            return Err(ContractError::InvalidLifecycleAction);
+        }
+
+        let event = RtpEvent::RequestLifecycle {
+            partnership_id: partnership_id.clone(),
+            trade_id: trade_id.clone(),
+            bank_id: bank_id.clone(),
+            action: action.clone(),
+        };
+        event.emit();
+
+        let is_agreed = self
+            .lifecycle_requests
+            .get(&trade_key)
+            .map(|request| request.bank_id != bank_id && request.action == action)
+            .unwrap_or_default();
+        if !is_agreed {
+            self.lifecycle_requests
+                .insert(trade_key, LifecycleRequest { bank_id, action });
+            return Ok(());
+        }
+        self.lifecycle_requests.remove(&trade_key);
+
+        let new_trade_id = match &action {
+            LifecycleAction::Roll { new_trade_id, .. } => new_trade_id.clone(),
+            LifecycleAction::Terminate { .. } => close_out_trade_id(&trade_id),
+        };
+        match action.clone() {
+            LifecycleAction::Roll {
+                new_trade_id,
+                value_date,
+                price,
+            } => {
+                let rolled_trade = trade.rolled(value_date.clone(), price);
+                self.replace_trade(&partnership_id, &trade_id, &new_trade_id, rolled_trade);
+
+                let event = RtpEvent::RollTrade {
+                    partnership_id,
+                    trade_id: trade_id.clone(),
+                    new_trade_id,
+                    value_date,
+                    price,
+                };
+                event.emit();
+            }
+            LifecycleAction::Terminate {
+                close_out,
+                value_date,
+            } => {
+                let closed_out_trade = trade.closed_out(close_out.clone(), value_date.clone());
+                self.replace_trade(
+                    &partnership_id,
+                    &trade_id,
+                    &close_out_trade_id(&trade_id),
+                    closed_out_trade,
+                );
+
+                let event = RtpEvent::TerminateTrade {
+                    partnership_id,
+                    trade_id: trade_id.clone(),
+                    close_out,
+                    value_date,
+                };
+                event.emit();
+            }
+        }
+
+        let factory_account_id = env::current_account_id();
+        let account_a_id: AccountId = format!("{}.{factory_account_id}", bank_ids[0])
+            .parse()
+            .unwrap();
+        let account_b_id: AccountId = format!("{}.{factory_account_id}", bank_ids[1])
+            .parse()
+            .unwrap();
+        rtp::ext(account_a_id)
+            .with_unused_gas_weight(1)
+            .apply_lifecycle(trade_id.clone(), action.clone())
+            .and(
+                rtp::ext(account_b_id)
+                    .with_unused_gas_weight(1)
+                    .apply_lifecycle(trade_id.clone(), action),
+            )
+            .then(Self::ext(factory_account_id).on_apply_lifecycle(
+                partnership_id,
+                trade_id,
+                new_trade_id,
+                is_missed,
+            ));
+
+        Ok(())
+    }
+
+    /// The lifecycle operation is only kept, once both bank contracts applied it.
+    /// Otherwise, the original trade is restored in place of the replacing trade,
+    /// so that the operation can be requested again.
+    #[private]
+    pub fn on_apply_lifecycle(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        new_trade_id: String,
+        is_missed: bool,
+        #[callback_result] lifecycle_a: Result<(), PromiseError>,
+        #[callback_result] lifecycle_b: Result<(), PromiseError>,
+    ) {
+        if lifecycle_a.is_ok() && lifecycle_b.is_ok() {
+            return;
+        }
+        self.release_exposure(&partnership_id, &new_trade_id);
+        self.partnership_trades
+            .remove(&(partnership_id.clone(), new_trade_id));
+        let status = if is_missed {
+            PartnershipTradeStatus::MissedCutOff
+        } else {
+            PartnershipTradeStatus::Matched
+        };
+        self.restore_exposure(&partnership_id, &trade_id, status);
+
+        let event = RtpEvent::LifecycleFailed {
+            partnership_id,
+            trade_id,
+            reason: "Bank contract did not apply the lifecycle operation".to_string(),
+        };
+        event.emit();
+    }
+}
+
+impl Contract {
+    /// Close the original trade and track the new trade as matched in its place.
+    fn replace_trade(
+        &mut self,
+        partnership_id: &str,
+        trade_id: &str,
+        new_trade_id: &str,
+        new_trade: PartnershipTrade,
+    ) {
+        self.release_exposure(partnership_id, trade_id);
+        self.exposure_mut(partnership_id, &new_trade.ccy).matched += new_trade.amount;
+        self.insert_partnership_trade(
+            (partnership_id.to_string(), new_trade_id.to_string()),
+            new_trade,
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::{
+    borsh::{self, BorshDeserialize},
+    env, near_bindgen,
+    store::{Lazy, UnorderedSet},
+};
+use rtp_contract_common::get_bank_id;
+
+/// State of the factory, before it tracked partnerships and their trades.
+#[derive(BorshDeserialize)]
+struct LegacyContract {
+    bank_ids: UnorderedSet<String>,
+    contract_code: Lazy<Vec<u8>>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Migrate the state of a factory, which only tracked the IDs of its banks and the bank contract.
+    /// The bank names cannot be derived from the bank IDs, so all of them need to be passed as `banks`.
+    /// The factory account remains the owner.
+    #[private]
+    #[init(ignore_state)]
+    #[handle_result]
+    pub fn migrate(banks: Vec<String>) -> Result<Self, ContractError> {
+        let legacy: LegacyContract = env::state_read()
+            .ok_or_else(|| ContractError::MigrationFailed("No legacy state found".to_string()))?;
+        let code = legacy.contract_code.get().clone();
+
+        let mut contract = Self::new();
+        contract.contract_code.set(code);
+        for bank in banks {
+            let bank_id = get_bank_id(&bank);
+            if !legacy.bank_ids.contains(&bank_id) {
+                return Err(ContractError::MigrationFailed(format!(
+                    "Unknown bank {bank}"
+                )));
+            }
+            contract.banks.insert(bank_id, bank);
+        }
+        if contract.banks.len() != legacy.bank_ids.len() {
+            return Err(ContractError::MigrationFailed(
+                "Names of all banks are required".to_string(),
+            ));
+        }
+        contract.bank_ids = legacy.bank_ids;
+
+        Ok(contract)
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{calendar::parse_date, rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId, Promise, PromiseError};
+use rtp_contract_common::{get_bank_id, Date, NdfFixing, PartnershipTradeStatus, Role, RtpEvent};
+
+#[near_bindgen]
+impl Contract {
+    /// Submit the fixing rate of an NDF, which can only be done by a rate provider.
+    /// The resulting net cash-settlement replaces the payments of the trade,
+    /// so it can be settled like any other trade.
+    #[handle_result]
+    pub fn submit_fixing(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        fixing_rate: f64,
+    ) -> Result<Promise, ContractError> {
+        self.assert_role(Role::RateProvider)?;
+        if !fixing_rate.is_finite() || fixing_rate <= 0. {
+            return Err(ContractError::InvalidFixingRate);
+        }
+        let partnership = self
+            .partnerships
+            .get(&partnership_id)
+            .ok_or(ContractError::PartnershipNotYetExists)?;
+        self.assert_not_paused(
+            &[
+                &get_bank_id(&partnership.bank_a),
+                &get_bank_id(&partnership.bank_b),
+            ],
+            Some(&partnership_id),
+        )?;
+
+        let trade_key = (partnership_id.clone(), trade_id.clone());
+        let trade = self
+            .partnership_trades
+            .get(&trade_key)
+            .ok_or(ContractError::InvalidTradeId)?;
+        let ndf = trade.ndf.as_ref().ok_or(ContractError::NotAnNdf)?;
+        if ndf.fixing.is_some() {
+            return Err(ContractError::NdfAlreadyFixed);
+        }
+        if trade.status != PartnershipTradeStatus::Matched {
+            return Err(ContractError::TradeNotSettleable);
+        }
+        let fixing_date = parse_date(&ndf.terms.fixing_date)?;
+        if fixing_date > Date::from_timestamp_ms(env::block_timestamp_ms()) {
+            return Err(ContractError::FixingDateNotReached(
+                ndf.terms.fixing_date.clone(),
+            ));
+        }
+
+        let trade = self.partnership_trades.get_mut(&trade_key).unwrap();
+        let ndf = trade.ndf.as_mut().unwrap();
+        let settlement = ndf.settle(fixing_rate);
+        let fixing = NdfFixing {
+            rate: fixing_rate,
+            settlement: settlement.clone(),
+        };
+        ndf.fixing = Some(fixing.clone());
+        trade.legs = vec![settlement.clone()];
+
+        let event = RtpEvent::SubmitFixing {
+            partnership_id: partnership_id.clone(),
+            trade_id: trade_id.clone(),
+            fixing: fixing.clone(),
+        };
+        event.emit();
+
+        let factory_account_id = env::current_account_id();
+        let payer_account_id: AccountId = format!("{}.{factory_account_id}", settlement.payer)
+            .parse()
+            .unwrap();
+        let receiver_account_id: AccountId =
+            format!("{}.{factory_account_id}", settlement.receiver)
+                .parse()
+                .unwrap();
+        Ok(rtp::ext(payer_account_id)
+            .with_unused_gas_weight(1)
+            .set_ndf_fixing(trade_id.clone(), fixing.clone())
+            .and(
+                rtp::ext(receiver_account_id)
+                    .with_unused_gas_weight(1)
+                    .set_ndf_fixing(trade_id.clone(), fixing),
+            )
+            .then(Self::ext(factory_account_id).on_submit_fixing(partnership_id, trade_id)))
+    }
+
+    /// The fixing is only kept, once both bank contracts stored it.
+    /// Otherwise, the NDF is reset, so that the fixing can be submitted again.
+    #[private]
+    pub fn on_submit_fixing(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        #[callback_result] fixing_payer: Result<(), PromiseError>,
+        #[callback_result] fixing_receiver: Result<(), PromiseError>,
+    ) {
+        if fixing_payer.is_ok() && fixing_receiver.is_ok() {
+            return;
+        }
+        let Some(trade) = self
+            .partnership_trades
+            .get_mut(&(partnership_id.clone(), trade_id.clone()))
+        else {
+            return;
+        };
+        if let Some(ndf) = trade.ndf.as_mut() {
+            ndf.fixing = None;
+        }
+        trade.legs = vec![];
+
+        let event = RtpEvent::FixingFailed {
+            partnership_id,
+            trade_id,
+            reason: "Bank contract did not store the fixing".to_string(),
+        };
+        event.emit();
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId, Promise, PromiseOrValue, PromiseResult};
+use rtp_contract_common::{
+    net_legs, BlockTime, NettingCycle, NettingCycleStatus, PartnershipTrade,
+    PartnershipTradeStatus, PaymentStatus, RtpEvent, SettlementInstruction,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[near_bindgen]
+impl Contract {
+    /// Net all matched and unsettled trades of a partnership with given value date.
+    /// Returns the ID of the newly opened netting cycle.
+    #[handle_result]
+    pub fn run_netting(
+        &mut self,
+        partnership_id: String,
+        value_date: String,
+    ) -> Result<u64, ContractError> {
+        self.assert_owner()?;
+        if !self.partnerships.contains_key(&partnership_id) {
+            return Err(ContractError::PartnershipNotYetExists);
+        }
+
+        let trades = self.nettable_trades(Some(&partnership_id), Some(&value_date));
+        if trades.is_empty() {
+            return Err(ContractError::NothingToNet);
+        }
+        let instructions = net_legs(trades.iter().flat_map(|(_, trade)| trade.legs.iter()));
+
+        Ok(self.open_netting_cycle(
+            Some(partnership_id),
+            Some(value_date),
+            trades.into_iter().map(|(trade_key, _)| trade_key).collect(),
+            instructions,
+        ))
+    }
+
+    /// Net all matched and unsettled trades across every partnership of all banks,
+    /// optionally restricted to given value date.
+    /// Returns the ID of the newly opened netting cycle.
+    #[handle_result]
+    pub fn run_multilateral_netting(
+        &mut self,
+        value_date: Option<String>,
+    ) -> Result<u64, ContractError> {
+        self.assert_owner()?;
+        let trades = self.multilateral_nettable_trades(value_date.as_deref());
+        if trades.is_empty() {
+            return Err(ContractError::NothingToNet);
+        }
+        let instructions = net_legs(trades.iter().flat_map(|(_, trade)| trade.legs.iter()));
+
+        Ok(self.open_netting_cycle(
+            None,
+            value_date,
+            trades.into_iter().map(|(trade_key, _)| trade_key).collect(),
+            instructions,
+        ))
+    }
+
+    /// Confirm a single net payment of a netting cycle on behalf of its payer or receiver.
+    /// Can only be called by the bank contract of either bank.
+    /// As soon as all net payments are confirmed, every underlying trade is marked as paid.
+    #[handle_result]
+    pub fn confirm_net_payment(
+        &mut self,
+        bank_id: String,
+        cycle_id: u64,
+        instruction_id: u32,
+    ) -> Result<PromiseOrValue<()>, ContractError> {
+        self.assert_bank(&bank_id)?;
+        if let Some((cycle, instruction)) = self.netting_cycles.get(&cycle_id).and_then(|cycle| {
+            let instruction = cycle.instructions.get(instruction_id as usize)?;
+            Some((cycle, instruction))
+        }) {
+            if instruction.payer != bank_id && instruction.receiver != bank_id {
+                return Err(ContractError::BankNotInNetPayment);
+            }
+            self.assert_not_paused(
+                &[&instruction.payer, &instruction.receiver],
+                cycle.partnership_id.as_deref(),
+            )?;
+        }
+        let cycle = self
+            .netting_cycles
+            .get(&cycle_id)
+            .ok_or(ContractError::InvalidNettingCycle)?;
+        if cycle.status != NettingCycleStatus::Open {
+            return Err(ContractError::NettingCycleNotOpen);
+        }
+        for trade_key in &cycle.trades {
+            if let Some(trade) = self.partnership_trades.get(trade_key) {
+                self.assert_payment_window(trade)?;
+            }
+        }
+        let cycle = self.netting_cycles.get_mut(&cycle_id).unwrap();
+        let instruction = cycle
+            .instructions
+            .get_mut(instruction_id as usize)
+            .ok_or(ContractError::InvalidSettlementInstruction)?;
+        if instruction.confirmed {
+            return Err(ContractError::NetPaymentAlreadyConfirmed);
+        }
+        instruction.confirmed = true;
+
+        let event = RtpEvent::ConfirmNetPayment {
+            cycle_id,
+            instruction_id,
+            instruction: instruction.clone(),
+        };
+        event.emit();
+
+        if cycle.is_confirmed() {
+            Ok(PromiseOrValue::Promise(self.settle_netting_cycle(cycle_id)))
+        } else {
+            Ok(PromiseOrValue::Value(()))
+        }
+    }
+
+    /// Mark the trades of a netting cycle as paid again, after the bank contracts failed to do so.
+    /// Its net payments have already been made, so its trades remain part of the cycle.
+    #[handle_result]
+    pub fn retry_netting_cycle(&mut self, cycle_id: u64) -> Result<Promise, ContractError> {
+        self.assert_owner()?;
+        let cycle = self
+            .netting_cycles
+            .get(&cycle_id)
+            .ok_or(ContractError::InvalidNettingCycle)?;
+        if cycle.status != NettingCycleStatus::Error {
+            return Err(ContractError::NettingCycleNotFailed);
+        }
+
+        Ok(self.settle_netting_cycle(cycle_id))
+    }
+
+    /// Cycles, which failed to be settled, keep their trades and can be retried
+    /// via `retry_netting_cycle`.
+    #[private]
+    pub fn on_settle_netting_cycle(&mut self, cycle_id: u64) {
+        let success = (0..env::promise_results_count())
+            .all(|index| matches!(env::promise_result(index), PromiseResult::Successful(_)));
+        let Some(cycle) = self.netting_cycles.get_mut(&cycle_id) else {
+            return;
+        };
+
+        if success {
+            cycle.status = NettingCycleStatus::Settled;
+            let trades = cycle.trades.clone();
+            let payment_status =
+                PaymentStatus::Confirmed(format!("Settled in netting cycle {cycle_id}"));
+            for (partnership_id, trade_id) in trades {
+                self.settle_exposure(&partnership_id, &trade_id, &payment_status);
+
+                let event = RtpEvent::SetPaymentStatus {
+                    partnership_id,
+                    trade_id,
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::now(),
+                };
+                event.emit();
+            }
+        } else {
+            cycle.status = NettingCycleStatus::Error;
+        }
+
+        let event = RtpEvent::SettleNettingCycle {
+            cycle_id,
+            status: self.netting_cycles.get(&cycle_id).unwrap().status.clone(),
+        };
+        event.emit();
+    }
+}
+
+impl Contract {
+    /// Matched trades, which are not yet settled and not part of any netting cycle,
+    /// PvP escrow or settlement queue. NDFs can only be netted after their fixing.
+    pub(crate) fn nettable_trades(
+        &self,
+        partnership_id: Option<&str>,
+        value_date: Option<&str>,
+    ) -> Vec<((String, String), PartnershipTrade)> {
+        self.partnership_trades
+            .iter()
+            .filter(|(trade_key, trade)| {
+                trade.status == PartnershipTradeStatus::Matched
+                    && !trade.legs.is_empty()
+                    && trade.netting_cycle.is_none()
+                    && !self.pvp_escrows.contains_key(*trade_key)
+                    && !self.settlement_queue.contains_key(*trade_key)
+                    && partnership_id.map(|id| trade_key.0 == id).unwrap_or(true)
+                    && value_date
+                        .map(|date| trade.value_date == date)
+                        .unwrap_or(true)
+            })
+            .map(|(trade_key, trade)| (trade_key.clone(), trade.clone()))
+            .collect()
+    }
+
+    /// Nettable trades of all partnerships, where both banks are still registered.
+    pub(crate) fn multilateral_nettable_trades(
+        &self,
+        value_date: Option<&str>,
+    ) -> Vec<((String, String), PartnershipTrade)> {
+        self.nettable_trades(None, value_date)
+            .into_iter()
+            .filter(|(_, trade)| {
+                trade.legs.iter().all(|leg| {
+                    self.bank_ids.contains(&leg.payer) && self.bank_ids.contains(&leg.receiver)
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn open_netting_cycle(
+        &mut self,
+        partnership_id: Option<String>,
+        value_date: Option<String>,
+        trades: Vec<(String, String)>,
+        instructions: Vec<SettlementInstruction>,
+    ) -> u64 {
+        let cycle_id = self.next_netting_cycle_id;
+        self.next_netting_cycle_id += 1;
+
+        for trade_key in trades.iter() {
+            if let Some(trade) = self.partnership_trades.get_mut(trade_key) {
+                trade.netting_cycle = Some(cycle_id);
+            }
+        }
+        let cycle = NettingCycle {
+            partnership_id: partnership_id.clone(),
+            value_date: value_date.clone(),
+            trades: trades.clone(),
+            instructions: instructions.clone(),
+            status: NettingCycleStatus::Open,
+        };
+        let is_confirmed = cycle.is_confirmed();
+        self.netting_cycles.insert(cycle_id, cycle);
+
+        let event = RtpEvent::NettingCycle {
+            cycle_id,
+            partnership_id,
+            value_date,
+            trades,
+            instructions,
+        };
+        event.emit();
+
+        // trades might fully offset each other, so that no payments need to be made
+        if is_confirmed {
+            self.settle_netting_cycle(cycle_id);
+        }
+
+        cycle_id
+    }
+
+    /// Marks every trade of a netting cycle as paid in the respective bank contracts.
+    fn settle_netting_cycle(&self, cycle_id: u64) -> Promise {
+        let cycle = self.netting_cycles.get(&cycle_id).unwrap();
+        self.settle_bank_trades(
+            &cycle.trades,
+            &format!("Settled in netting cycle {cycle_id}"),
+        )
+        .then(Self::ext(env::current_account_id()).on_settle_netting_cycle(cycle_id))
+    }
+
+    /// Marks given trades as paid in the bank contracts of all involved banks.
+    pub(crate) fn settle_bank_trades(
+        &self,
+        trade_keys: &[(String, String)],
+        message: &str,
+    ) -> Promise {
+        let mut trade_ids_by_bank: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for trade_key in trade_keys.iter() {
+            let trade = self.partnership_trades.get(trade_key).unwrap();
+            for leg in trade.legs.iter() {
+                for bank_id in [&leg.payer, &leg.receiver] {
+                    trade_ids_by_bank
+                        .entry(bank_id.clone())
+                        .or_default()
+                        .insert(trade_key.1.clone());
+                }
+            }
+        }
+
+        let factory_account_id = env::current_account_id();
+        trade_ids_by_bank
+            .into_iter()
+            .map(|(bank_id, trade_ids)| {
+                let account_id: AccountId =
+                    format!("{bank_id}.{factory_account_id}").parse().unwrap();
+                rtp::ext(account_id)
+                    .with_unused_gas_weight(1)
+                    .settle_trades(trade_ids.into_iter().collect(), message.to_string())
+            })
+            .reduce(|a, b| a.and(b))
+            .unwrap()
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId, Promise};
+use rtp_contract_common::{AdminOperation, AdminProposal, MultisigConfig, RtpEvent};
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
+    /// Propose a new owner, which takes over as soon as it accepts the ownership.
+    /// A pending proposal is replaced.
+    #[handle_result]
+    pub fn propose_owner(&mut self, new_owner: AccountId) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_propose_owner(new_owner);
+
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn accept_ownership(&mut self) -> Result<(), ContractError> {
+        let new_owner = env::predecessor_account_id();
+        if self.pending_owner.as_ref() != Some(&new_owner) {
+            return Err(ContractError::NotPendingOwner);
+        }
+
+        let old_owner = std::mem::replace(&mut self.owner, new_owner.clone());
+        self.pending_owner = None;
+
+        let event = RtpEvent::TransferOwnership {
+            old_owner: old_owner.to_string(),
+            new_owner: new_owner.to_string(),
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    /// Deploy the factory contract from input.
+    #[handle_result]
+    pub fn upgrade(&mut self) -> Result<Promise, ContractError> {
+        self.assert_critical()?;
+        let input = env::input().ok_or(ContractError::NoInput)?;
+
+        Ok(Promise::new(env::current_account_id()).deploy_contract(input))
+    }
+
+    /// Require the approval of `threshold` admins for every critical function,
+    /// or switch back to the sole approval of the owner, if `config` is not set.
+    #[handle_result]
+    pub fn set_multisig(&mut self, config: Option<MultisigConfig>) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_multisig(config)
+    }
+
+    pub fn get_multisig(&self) -> Option<MultisigConfig> {
+        self.multisig.clone()
+    }
+
+    /// Propose a critical operation on behalf of an admin, whose proposal counts as its approval.
+    /// Returns the ID of the proposal.
+    #[handle_result]
+    pub fn propose_operation(&mut self, operation: AdminOperation) -> Result<u64, ContractError> {
+        let config = self
+            .multisig
+            .as_ref()
+            .ok_or(ContractError::MultisigNotEnabled)?;
+        let proposer = env::predecessor_account_id();
+        if !config.admins.contains(&proposer) {
+            return Err(ContractError::NotMultisigAdmin);
+        }
+
+        let proposal_id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        let proposal = AdminProposal {
+            operation,
+            proposer: proposer.clone(),
+            approvals: vec![proposer.clone()],
+            expires_at_ms: env::block_timestamp_ms() + config.proposal_ttl_ms,
+            executed: false,
+        };
+
+        let event = RtpEvent::ProposeOperation {
+            proposal_id,
+            proposer: proposer.to_string(),
+            operation: proposal.operation.name().to_string(),
+            expires_at_ms: proposal.expires_at_ms,
+        };
+        event.emit();
+
+        self.admin_proposals.insert(proposal_id, proposal);
+        self.execute_if_approved(proposal_id)?;
+
+        Ok(proposal_id)
+    }
+
+    /// Approve a proposal on behalf of an admin.
+    /// The operation is executed, as soon as it has been approved by enough admins.
+    #[handle_result]
+    pub fn approve_operation(&mut self, proposal_id: u64) -> Result<(), ContractError> {
+        let config = self
+            .multisig
+            .as_ref()
+            .ok_or(ContractError::MultisigNotEnabled)?;
+        let admin = env::predecessor_account_id();
+        if !config.admins.contains(&admin) {
+            return Err(ContractError::NotMultisigAdmin);
+        }
+        let proposal = self
+            .admin_proposals
+            .get_mut(&proposal_id)
+            .ok_or(ContractError::InvalidProposalId)?;
+        if proposal.executed {
+            return Err(ContractError::ProposalAlreadyExecuted);
+        }
+        if proposal.is_expired(env::block_timestamp_ms()) {
+            return Err(ContractError::ProposalExpired);
+        }
+        if proposal.approvals.contains(&admin) {
+            return Err(ContractError::AlreadyApproved);
+        }
+        proposal.approvals.push(admin.clone());
+
+        let event = RtpEvent::ApproveOperation {
+            proposal_id,
+            admin: admin.to_string(),
+            approvals: proposal.approval_count(config),
+        };
+        event.emit();
+
+        self.execute_if_approved(proposal_id)
+    }
+
+    pub fn get_admin_proposal(&self, proposal_id: u64) -> Option<AdminProposal> {
+        self.admin_proposals.get(&proposal_id).cloned()
+    }
+
+    pub fn get_admin_proposals(
+        &self,
+        skip: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(u64, AdminProposal)> {
+        self.admin_proposals
+            .iter()
+            .skip(skip.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(20) as usize)
+            .map(|(proposal_id, proposal)| (*proposal_id, proposal.clone()))
+            .collect()
+    }
+}
+
+impl Contract {
+    /// The caller needs to be the owner.
+    pub(crate) fn assert_owner(&self) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.owner {
+            return Err(ContractError::NotOwner);
+        }
+
+        Ok(())
+    }
+
+    /// Critical functions can only be called by the owner directly, as long as no multisig is configured.
+    pub(crate) fn assert_critical(&self) -> Result<(), ContractError> {
+        self.assert_owner()?;
+        if self.multisig.is_some() {
+            return Err(ContractError::MultisigRequired);
+        }
+
+        Ok(())
+    }
+
+    fn apply_propose_owner(&mut self, new_owner: AccountId) {
+        self.pending_owner = Some(new_owner.clone());
+
+        let event = RtpEvent::ProposeOwner {
+            owner: self.owner.to_string(),
+            new_owner: new_owner.to_string(),
+        };
+        event.emit();
+    }
+
+    fn apply_multisig(&mut self, config: Option<MultisigConfig>) -> Result<(), ContractError> {
+        if let Some(config) = &config {
+            if !config.is_valid() {
+                return Err(ContractError::InvalidMultisigConfig);
+            }
+        }
+        self.multisig = config;
+
+        Ok(())
+    }
+
+    /// Execute the operation of a proposal, once the threshold of the current admin set is reached.
+    fn execute_if_approved(&mut self, proposal_id: u64) -> Result<(), ContractError> {
+        let Some(config) = &self.multisig else {
+            return Ok(());
+        };
+        let proposal = self.admin_proposals.get_mut(&proposal_id).unwrap();
+        if proposal.approval_count(config) < config.threshold {
+            return Ok(());
+        }
+        proposal.executed = true;
+        let operation = proposal.operation.clone();
+
+        match operation.clone() {
+            AdminOperation::StoreContract { code } => {
+                self.contract_code.set(code.into());
+            }
+            AdminOperation::Upgrade { code } => {
+                Promise::new(env::current_account_id()).deploy_contract(code.into());
+            }
+            AdminOperation::RemoveBank { bank_id } => {
+                self.archive_and_remove_bank(bank_id)?;
+            }
+            AdminOperation::GrantRole { role, account_id } => {
+                self.roles.insert((role, account_id));
+            }
+            AdminOperation::RevokeRole { role, account_id } => {
+                self.roles.remove(&(role, account_id));
+            }
+            AdminOperation::SetMultisig { config } => {
+                self.apply_multisig(config)?;
+            }
+            AdminOperation::ClearStorage => {
+                self.apply_clear_storage();
+            }
+            AdminOperation::SuspendBank { bank_id, suspended } => {
+                self.apply_suspend_bank(bank_id, suspended)?;
+            }
+            AdminOperation::SetHolidays {
+                financial_centre,
+                holidays,
+            } => {
+                self.apply_holidays(financial_centre, holidays)?;
+            }
+            AdminOperation::SetPaymentWindow { ccy, window } => {
+                self.apply_payment_window(ccy, window)?;
+            }
+            AdminOperation::SetExposureLimit {
+                partnership_id,
+                ccy,
+                limit,
+            } => {
+                self.apply_exposure_limit(partnership_id, ccy, limit)?;
+            }
+            AdminOperation::SetCurrencyToken { ccy, token } => {
+                self.apply_currency_token(ccy, token);
+            }
+            AdminOperation::SetSettlementAccount {
+                bank_id,
+                account_id,
+            } => {
+                self.apply_settlement_account(bank_id, account_id)?;
+            }
+            AdminOperation::WithdrawLiquidity {
+                bank_id,
+                ccy,
+                amount,
+                receiver_id,
+            } => {
+                self.apply_withdraw_liquidity(bank_id, ccy, amount, receiver_id)?;
+            }
+            AdminOperation::ProposeOwner { new_owner } => {
+                self.apply_propose_owner(new_owner);
+            }
+        }
+
+        let event = RtpEvent::ExecuteOperation {
+            proposal_id,
+            operation: operation.name().to_string(),
+        };
+        event.emit();
+
+        Ok(())
+    }
+}
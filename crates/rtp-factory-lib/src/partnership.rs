@@ -0,0 +1,107 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::near_bindgen;
+use rtp_contract_common::{
+    get_bank_id, get_partnership_id, Partnership, PartnershipStatus, PartnershipTerms, RtpEvent,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Propose a partnership with given terms to the counterparty.
+    /// The partnership only becomes active after the counterparty accepted the terms.
+    /// Proposing again before acceptance replaces the terms of a pending proposal.
+    /// Can only be called by the bank contract on behalf of its admin.
+    #[handle_result]
+    pub fn propose_partnership(
+        &mut self,
+        bank_id: String,
+        counterparty: String,
+        terms: PartnershipTerms,
+    ) -> Result<(), ContractError> {
+        self.assert_bank(&bank_id)?;
+        let bank = self
+            .banks
+            .get(&bank_id)
+            .ok_or(ContractError::BankNotYetExists)?
+            .clone();
+        if bank == counterparty {
+            return Err(ContractError::InvalidCounterparty);
+        }
+        if !self.bank_ids.contains(&get_bank_id(&counterparty)) {
+            return Err(ContractError::BankNotYetExists);
+        }
+
+        let partnership_id = get_partnership_id(bank.clone(), counterparty.clone());
+        if let Some(partnership) = self.partnerships.get(&partnership_id) {
+            if partnership.is_active() {
+                return Err(ContractError::PartnershipAlreadyExists);
+            }
+        }
+
+        let (bank_a, bank_b) = if bank < counterparty {
+            (bank, counterparty)
+        } else {
+            (counterparty, bank)
+        };
+        self.partnerships.insert(
+            partnership_id.clone(),
+            Partnership {
+                bank_a,
+                bank_b,
+                terms: terms.clone(),
+                status: PartnershipStatus::Proposed(bank_id.clone()),
+            },
+        );
+
+        let event = RtpEvent::ProposePartnership {
+            partnership_id,
+            bank_id,
+            terms,
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    /// Accept the partnership terms, which have been proposed by the counterparty.
+    /// Can only be called by the bank contract on behalf of its admin.
+    #[handle_result]
+    pub fn accept_partnership(
+        &mut self,
+        bank_id: String,
+        counterparty: String,
+    ) -> Result<(), ContractError> {
+        self.assert_bank(&bank_id)?;
+        let bank = self
+            .banks
+            .get(&bank_id)
+            .ok_or(ContractError::BankNotYetExists)?
+            .clone();
+        if bank == counterparty {
+            return Err(ContractError::InvalidCounterparty);
+        }
+
+        let partnership_id = get_partnership_id(bank, counterparty.clone());
+        let partnership = self
+            .partnerships
+            .get_mut(&partnership_id)
+            .ok_or(ContractError::PartnershipNotYetExists)?;
+        match &partnership.status {
+            PartnershipStatus::Active => return Err(ContractError::PartnershipAlreadyExists),
+            PartnershipStatus::Proposed(proposer) if *proposer != get_bank_id(&counterparty) => {
+                return Err(ContractError::PartnershipNotProposedByCounterparty);
+            }
+            PartnershipStatus::Proposed(_) => {}
+        }
+        partnership.status = PartnershipStatus::Active;
+
+        let event = RtpEvent::NewPartnership {
+            partnership_id,
+            bank_a: partnership.bank_a.clone(),
+            bank_b: partnership.bank_b.clone(),
+            terms: partnership.terms.clone(),
+        };
+        event.emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{rtp, Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId};
+use rtp_contract_common::{PauseScope, Role, RtpEvent};
+
+#[near_bindgen]
+impl Contract {
+    /// Halt trading, status changes and payment confirmations in given scope.
+    /// Pausing a bank also pauses its bank contract.
+    #[handle_result]
+    pub fn pause(&mut self, scope: PauseScope) -> Result<(), ContractError> {
+        self.set_paused(scope, true)
+    }
+
+    /// Lift the pause of given scope. Pauses of other scopes remain in effect.
+    #[handle_result]
+    pub fn unpause(&mut self, scope: PauseScope) -> Result<(), ContractError> {
+        self.set_paused(scope, false)
+    }
+
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused_scopes.contains(&scope)
+    }
+
+    pub fn get_paused_scopes(&self) -> Vec<PauseScope> {
+        self.paused_scopes.iter().cloned().collect()
+    }
+}
+
+impl Contract {
+    /// Operations on given banks and partnership are rejected, while any of their scopes is paused.
+    pub(crate) fn assert_not_paused(
+        &self,
+        bank_ids: &[&str],
+        partnership_id: Option<&str>,
+    ) -> Result<(), ContractError> {
+        let scopes = [PauseScope::Global]
+            .into_iter()
+            .chain(bank_ids.iter().map(|bank_id| PauseScope::Bank {
+                bank_id: bank_id.to_string(),
+            }))
+            .chain(
+                partnership_id.map(|partnership_id| PauseScope::Partnership {
+                    partnership_id: partnership_id.to_string(),
+                }),
+            );
+        for scope in scopes {
+            if self.paused_scopes.contains(&scope) {
+                return Err(ContractError::Paused(scope));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Only the owner and accounts with the `Pauser` role can pause and unpause.
+    fn set_paused(&mut self, scope: PauseScope, paused: bool) -> Result<(), ContractError> {
+        let account_id = env::predecessor_account_id();
+        if account_id != self.owner {
+            self.assert_role(Role::Pauser)?;
+        }
+        let is_known = match &scope {
+            PauseScope::Global => true,
+            PauseScope::Bank { bank_id } => self.bank_ids.contains(bank_id),
+            PauseScope::Partnership { partnership_id } => {
+                self.partnerships.contains_key(partnership_id)
+            }
+        };
+        if !is_known {
+            return Err(ContractError::InvalidPauseScope);
+        }
+
+        if let PauseScope::Bank { bank_id } = &scope {
+            let factory_account_id = env::current_account_id();
+            let bank_account_id: AccountId =
+                format!("{bank_id}.{factory_account_id}").parse().unwrap();
+            rtp::ext(bank_account_id)
+                .with_unused_gas_weight(1)
+                .set_paused(paused);
+        }
+
+        let event = if paused {
+            self.paused_scopes.insert(scope.clone());
+            RtpEvent::Pause {
+                scope,
+                account_id: account_id.to_string(),
+            }
+        } else {
+            self.paused_scopes.remove(&scope);
+            RtpEvent::Unpause {
+                scope,
+                account_id: account_id.to_string(),
+            }
+        };
+        event.emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{ft, rtp, Contract, ContractError, ContractExt, FT_TRANSFER_GAS};
+use near_sdk::{
+    env,
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    AccountId, Balance, Promise, PromiseError, PromiseOrValue,
+};
+use rtp_contract_common::{
+    BlockTime, CurrencyToken, EscrowLeg, PartnershipTradeStatus, PaymentStatus, PvpEscrow, RtpEvent,
+};
+
+/// Message attached to `ft_transfer_call`, when depositing tokenized cash.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(untagged)]
+pub enum FtTransferMsg {
+    Pvp(PvpDepositMsg),
+    Liquidity(LiquidityDepositMsg),
+}
+
+/// Deposit into escrow for a payment-versus-payment settlement of a trade.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PvpDepositMsg {
+    pub partnership_id: String,
+    pub trade_id: String,
+    /// Bank ID of the paying bank.
+    pub bank_id: String,
+}
+
+/// Deposit into the liquidity of a bank, which is used by its settlement queue.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LiquidityDepositMsg {
+    pub bank_id: String,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Register the NEP-141 token, which represents tokenized cash of given currency.
+    /// Removes the token, if not set.
+    #[handle_result]
+    pub fn set_currency_token(
+        &mut self,
+        ccy: String,
+        token: Option<CurrencyToken>,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_currency_token(ccy, token);
+
+        Ok(())
+    }
+
+    /// Register the account, which funds the payment legs of a bank and receives its payouts.
+    /// Removes the account, if not set.
+    #[handle_result]
+    pub fn set_settlement_account(
+        &mut self,
+        bank_id: String,
+        account_id: Option<AccountId>,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_settlement_account(bank_id, account_id)
+    }
+
+    /// Deposit tokenized cash either into escrow for a payment-versus-payment settlement
+    /// or into the liquidity of a bank for its settlement queue.
+    /// Tokens, which have not been used, are returned.
+    #[handle_result]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> Result<PromiseOrValue<U128>, ContractError> {
+        let token_id = env::predecessor_account_id();
+        let ccy = self
+            .currency_tokens
+            .iter()
+            .find(|(_, token)| token.token_id == token_id)
+            .map(|(ccy, _)| ccy.clone())
+            .ok_or(ContractError::CurrencyNotTokenized)?;
+
+        let unused = match serde_json::from_str(&msg)
+            .map_err(|_| ContractError::InvalidFtTransferMessage)?
+        {
+            FtTransferMsg::Pvp(msg) => self.deposit_pvp(sender_id, ccy, amount.0, msg)?,
+            FtTransferMsg::Liquidity(msg) => {
+                self.deposit_liquidity(msg.bank_id, ccy, amount.0)?;
+                0
+            }
+        };
+
+        Ok(PromiseOrValue::Value(U128(unused)))
+    }
+
+    /// Refund both legs of a payment-versus-payment settlement, e.g. if the counterparty never funded its leg.
+    /// Refunded tokens can be withdrawn by the depositors.
+    #[handle_result]
+    pub fn refund_pvp(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+    ) -> Result<(), ContractError> {
+        self.assert_owner()?;
+        let escrow = self
+            .pvp_escrows
+            .remove(&(partnership_id.clone(), trade_id.clone()))
+            .ok_or(ContractError::InvalidTradeId)?;
+        for escrow_leg in escrow.legs {
+            if let Some(depositor) = escrow_leg.depositor {
+                self.credit_token_balance(depositor, escrow_leg.token_id, escrow_leg.deposited.0);
+            }
+        }
+
+        let event = RtpEvent::RefundPvp {
+            partnership_id,
+            trade_id,
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    /// Withdraw tokens, which have been paid out or refunded to the caller.
+    /// Withdraws the full balance, if `amount` is not set.
+    #[handle_result]
+    pub fn ft_withdraw(
+        &mut self,
+        token_id: AccountId,
+        amount: Option<U128>,
+    ) -> Result<Promise, ContractError> {
+        let account_id = env::predecessor_account_id();
+        let balance_key = (account_id.clone(), token_id.clone());
+        let balance = self
+            .token_balances
+            .get(&balance_key)
+            .copied()
+            .unwrap_or_default();
+        let amount = amount.map(|amount| amount.0).unwrap_or(balance);
+        if amount == 0 || amount > balance {
+            return Err(ContractError::NotEnoughBalance(amount, balance));
+        }
+        if amount == balance {
+            self.token_balances.remove(&balance_key);
+        } else {
+            self.token_balances.insert(balance_key, balance - amount);
+        }
+
+        Ok(ft::ext(token_id.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(FT_TRANSFER_GAS)
+            .ft_transfer(account_id.clone(), U128(amount), None)
+            .then(Self::ext(env::current_account_id()).on_ft_withdraw(
+                account_id,
+                token_id,
+                U128(amount),
+            )))
+    }
+
+    #[private]
+    pub fn on_ft_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+        #[callback_result] callback_res: Result<(), PromiseError>,
+    ) {
+        if callback_res.is_err() {
+            self.credit_token_balance(account_id, token_id, amount.0);
+        }
+    }
+
+    /// Tokens are only paid out, once both bank contracts marked the trade as paid.
+    /// Otherwise, the escrow is restored, so that the deposits can be refunded.
+    #[allow(clippy::too_many_arguments)]
+    #[private]
+    pub fn on_settle_pvp(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        account_a_id: AccountId,
+        account_b_id: AccountId,
+        escrow: PvpEscrow,
+        #[callback_result] settlement_a: Result<(), PromiseError>,
+        #[callback_result] settlement_b: Result<(), PromiseError>,
+    ) {
+        let payment_status = if settlement_a.is_err() || settlement_b.is_err() {
+            rtp::ext(account_a_id).set_payment_status(trade_id.clone(), PaymentStatus::Error);
+            rtp::ext(account_b_id).set_payment_status(trade_id.clone(), PaymentStatus::Error);
+            self.settle_exposure(&partnership_id, &trade_id, &PaymentStatus::Error);
+            self.pvp_escrows
+                .insert((partnership_id.clone(), trade_id.clone()), escrow);
+            PaymentStatus::Error
+        } else {
+            self.pay_out_pvp(escrow);
+
+            let event = RtpEvent::SettlePvp {
+                partnership_id: partnership_id.clone(),
+                trade_id: trade_id.clone(),
+            };
+            event.emit();
+
+            PaymentStatus::Confirmed("Settled via PvP".to_string())
+        };
+        let event = RtpEvent::SetPaymentStatus {
+            partnership_id,
+            trade_id,
+            payment_status,
+            block_time: BlockTime::now(),
+        };
+        event.emit();
+    }
+}
+
+impl Contract {
+    pub(crate) fn apply_settlement_account(
+        &mut self,
+        bank_id: String,
+        account_id: Option<AccountId>,
+    ) -> Result<(), ContractError> {
+        if !self.bank_ids.contains(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+        if let Some(account_id) = account_id {
+            self.settlement_accounts.insert(bank_id, account_id);
+        } else {
+            self.settlement_accounts.remove(&bank_id);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn apply_currency_token(&mut self, ccy: String, token: Option<CurrencyToken>) {
+        if let Some(token) = token {
+            self.currency_tokens.insert(ccy, token);
+        } else {
+            self.currency_tokens.remove(&ccy);
+        }
+    }
+
+    /// As soon as both legs of a matched trade are funded, they are swapped atomically.
+    /// Returns the amount exceeding the required deposit.
+    fn deposit_pvp(
+        &mut self,
+        sender_id: AccountId,
+        ccy: String,
+        amount: Balance,
+        msg: PvpDepositMsg,
+    ) -> Result<Balance, ContractError> {
+        let PvpDepositMsg {
+            partnership_id,
+            trade_id,
+            bank_id,
+        } = msg;
+        let trade_key = (partnership_id.clone(), trade_id.clone());
+        let trade = self
+            .partnership_trades
+            .get(&trade_key)
+            .ok_or(ContractError::InvalidTradeId)?;
+        if trade.status != PartnershipTradeStatus::Matched
+            || trade.netting_cycle.is_some()
+            || self.settlement_queue.contains_key(&trade_key)
+        {
+            return Err(ContractError::TradeNotSettleable);
+        }
+        let bank_ids: Vec<&str> = trade
+            .legs
+            .iter()
+            .flat_map(|leg| [leg.payer.as_str(), leg.receiver.as_str()])
+            .collect();
+        self.assert_not_paused(&bank_ids, Some(&partnership_id))?;
+        self.assert_payment_window(trade)?;
+        if self.settlement_accounts.get(&bank_id) != Some(&sender_id) {
+            return Err(ContractError::NotSettlementAccount);
+        }
+        if !self.pvp_escrows.contains_key(&trade_key) {
+            let escrow = self.new_pvp_escrow(&trade_key)?;
+            self.pvp_escrows.insert(trade_key.clone(), escrow);
+        }
+
+        let escrow = self.pvp_escrows.get_mut(&trade_key).unwrap();
+        let escrow_leg = escrow
+            .legs
+            .iter_mut()
+            .find(|escrow_leg| escrow_leg.leg.payer == bank_id && escrow_leg.leg.ccy == ccy)
+            .ok_or(ContractError::InvalidPvpLeg)?;
+        if escrow_leg
+            .depositor
+            .as_ref()
+            .map(|depositor| *depositor != sender_id)
+            .unwrap_or_default()
+        {
+            return Err(ContractError::InvalidPvpDepositor);
+        }
+        let accepted = amount.min(escrow_leg.required.0 - escrow_leg.deposited.0);
+        escrow_leg.deposited.0 += accepted;
+        escrow_leg.depositor = Some(sender_id);
+        let is_funded = escrow.is_funded();
+
+        let event = RtpEvent::DepositPvp {
+            partnership_id: partnership_id.clone(),
+            trade_id: trade_id.clone(),
+            bank_id,
+            ccy,
+            amount: U128(accepted),
+        };
+        event.emit();
+
+        if is_funded {
+            self.settle_pvp(partnership_id, trade_id);
+        }
+
+        Ok(amount - accepted)
+    }
+
+    fn new_pvp_escrow(&self, trade_key: &(String, String)) -> Result<PvpEscrow, ContractError> {
+        let trade = self.partnership_trades.get(trade_key).unwrap();
+        if trade.legs.len() != 2 {
+            return Err(ContractError::PvpNotSupported);
+        }
+        let legs = trade
+            .legs
+            .iter()
+            .map(|leg| {
+                let token = self
+                    .currency_tokens
+                    .get(&leg.ccy)
+                    .ok_or(ContractError::CurrencyNotTokenized)?;
+                Ok(EscrowLeg {
+                    leg: leg.clone(),
+                    token_id: token.token_id.clone(),
+                    required: U128(token.to_token_amount(leg.amount)),
+                    deposited: U128(0),
+                    depositor: None,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PvpEscrow { legs })
+    }
+
+    /// Mark the trade as paid in both bank contracts, before both funded legs are swapped.
+    fn settle_pvp(&mut self, partnership_id: String, trade_id: String) {
+        let trade_key = (partnership_id.clone(), trade_id.clone());
+        let escrow = self.pvp_escrows.remove(&trade_key).unwrap();
+        let payment_status = PaymentStatus::Confirmed("Settled via PvP".to_string());
+        self.settle_exposure(&partnership_id, &trade_id, &payment_status);
+
+        let factory_account_id = env::current_account_id();
+        let leg = &escrow.legs[0].leg;
+        let account_a_id: AccountId = format!("{}.{factory_account_id}", leg.payer)
+            .parse()
+            .unwrap();
+        let account_b_id: AccountId = format!("{}.{factory_account_id}", leg.receiver)
+            .parse()
+            .unwrap();
+        let message = "Settled via PvP".to_string();
+        rtp::ext(account_a_id.clone())
+            .settle_trades(vec![trade_id.clone()], message.clone())
+            .and(rtp::ext(account_b_id.clone()).settle_trades(vec![trade_id.clone()], message))
+            .then(Self::ext(factory_account_id).on_settle_pvp(
+                partnership_id,
+                trade_id,
+                account_a_id,
+                account_b_id,
+                escrow,
+            ));
+    }
+
+    /// Swap both funded legs of an escrow.
+    fn pay_out_pvp(&mut self, escrow: PvpEscrow) {
+        for escrow_leg in escrow.legs.iter() {
+            // the receiver gets paid out to the account, which funded its own leg
+            let receiver = escrow
+                .legs
+                .iter()
+                .find(|other| other.leg.payer == escrow_leg.leg.receiver)
+                .and_then(|other| other.depositor.clone())
+                .unwrap();
+            self.credit_token_balance(
+                receiver,
+                escrow_leg.token_id.clone(),
+                escrow_leg.deposited.0,
+            );
+        }
+    }
+
+    pub(crate) fn credit_token_balance(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: u128,
+    ) {
+        if amount == 0 {
+            return;
+        }
+        let balance_key = (account_id, token_id);
+        let balance = self
+            .token_balances
+            .get(&balance_key)
+            .copied()
+            .unwrap_or_default();
+        self.token_balances.insert(balance_key, balance + amount);
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Balance, PromiseResult};
+use rtp_contract_common::{
+    resolve_gridlock, settle_queue, BlockTime, Liquidity, PartnershipTradeStatus, PaymentStatus,
+    QueueStatus, QueuedPayment, QueuedSettlement, RtpEvent,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Queue a matched trade for settlement from the liquidity of its paying banks.
+    /// Queueing an already queued trade changes its priority, but keeps its position
+    /// among trades with the same priority.
+    #[handle_result]
+    pub fn queue_settlement(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        priority: u8,
+    ) -> Result<(), ContractError> {
+        self.assert_owner()?;
+        let trade_key = (partnership_id.clone(), trade_id.clone());
+        let trade = self
+            .partnership_trades
+            .get(&trade_key)
+            .ok_or(ContractError::InvalidTradeId)?;
+        if trade.status != PartnershipTradeStatus::Matched
+            || trade.legs.is_empty()
+            || trade.netting_cycle.is_some()
+            || self.pvp_escrows.contains_key(&trade_key)
+        {
+            return Err(ContractError::TradeNotSettleable);
+        }
+        let payments = trade
+            .legs
+            .iter()
+            .map(|leg| {
+                let token = self
+                    .currency_tokens
+                    .get(&leg.ccy)
+                    .ok_or(ContractError::CurrencyNotTokenized)?;
+                Ok(QueuedPayment {
+                    leg: leg.clone(),
+                    amount: U128(token.to_token_amount(leg.amount)),
+                })
+            })
+            .collect::<Result<Vec<_>, ContractError>>()?;
+
+        let sequence = if let Some(settlement) = self.settlement_queue.get(&trade_key) {
+            settlement.sequence
+        } else {
+            self.next_queue_sequence += 1;
+            self.next_queue_sequence
+        };
+        let settlement = QueuedSettlement {
+            priority,
+            sequence,
+            payments,
+        };
+        for payment in settlement.payments.iter() {
+            let reason = self.queue_reason(payment);
+            Self::emit_queue_event(&trade_key, payment, priority, QueueStatus::Queued(reason));
+        }
+        self.insert_queued_settlement(trade_key, settlement);
+
+        self.process_queue(None);
+
+        Ok(())
+    }
+
+    /// Settle those of the first `limit` queued trades in queue order, which can be covered
+    /// by the liquidity of their paying banks. Trades, which cannot be settled on their own, might still offset
+    /// each other, so that they can be settled together.
+    /// The queue is also processed on every deposit of liquidity and every newly queued trade.
+    #[handle_result]
+    pub fn process_settlement_queue(&mut self, limit: Option<u32>) -> Result<(), ContractError> {
+        self.assert_owner()?;
+        self.process_queue(limit);
+
+        Ok(())
+    }
+
+    /// Remove a trade from the settlement queue.
+    #[handle_result]
+    pub fn cancel_queued_settlement(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+    ) -> Result<(), ContractError> {
+        self.assert_owner()?;
+        self.remove_queued_settlement(partnership_id, trade_id)
+    }
+
+    /// Withdraw liquidity of a bank. The tokens can then be withdrawn by `receiver_id` via `ft_withdraw`.
+    #[handle_result]
+    pub fn withdraw_liquidity(
+        &mut self,
+        bank_id: String,
+        ccy: String,
+        amount: U128,
+        receiver_id: AccountId,
+    ) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.apply_withdraw_liquidity(bank_id, ccy, amount, receiver_id)
+    }
+
+    /// Settlements, which failed to be marked as paid in any bank contract,
+    /// are put back into the queue and their payments are reversed.
+    #[private]
+    pub fn on_settle_queued(
+        &mut self,
+        settlements: Vec<((String, String), QueuedSettlement)>,
+        message: String,
+    ) {
+        let success = (0..env::promise_results_count())
+            .all(|index| matches!(env::promise_result(index), PromiseResult::Successful(_)));
+        let payment_status = if success {
+            PaymentStatus::Confirmed(message)
+        } else {
+            PaymentStatus::Error
+        };
+
+        for ((partnership_id, trade_id), settlement) in settlements {
+            self.settle_exposure(&partnership_id, &trade_id, &payment_status);
+            if !success {
+                self.requeue_settlement((partnership_id.clone(), trade_id.clone()), settlement);
+            }
+
+            let event = RtpEvent::SetPaymentStatus {
+                partnership_id,
+                trade_id,
+                payment_status: payment_status.clone(),
+                block_time: BlockTime::now(),
+            };
+            event.emit();
+        }
+    }
+}
+
+impl Contract {
+    /// Settles those of the first `limit` queued trades in queue order,
+    /// which can be covered by the liquidity of their paying banks.
+    pub(crate) fn process_queue(&mut self, limit: Option<u32>) {
+        let trade_keys: Vec<_> = self
+            .settlement_queue_order
+            .values()
+            .take(limit.unwrap_or(20) as usize)
+            .cloned()
+            .collect();
+        let mut queue = Vec::with_capacity(trade_keys.len());
+        for trade_key in trade_keys {
+            let Some(trade) = self
+                .partnership_trades
+                .get(&trade_key)
+                .filter(|trade| trade.status == PartnershipTradeStatus::Matched)
+            else {
+                self.remove_queued_settlement(trade_key.0, trade_key.1)
+                    .unwrap();
+                continue;
+            };
+            // settlements outside the operating window of any of their currencies have to wait
+            if self.is_payment_window_open(trade) {
+                let settlement = self.settlement_queue[&trade_key].clone();
+                queue.push((trade_key, settlement));
+            }
+        }
+        let mut liquidity: Liquidity = queue
+            .iter()
+            .flat_map(|(_, settlement)| settlement.payments.iter())
+            .flat_map(|payment| {
+                [
+                    (payment.leg.payer.clone(), payment.leg.ccy.clone()),
+                    (payment.leg.receiver.clone(), payment.leg.ccy.clone()),
+                ]
+            })
+            .map(|key| {
+                let available = self.bank_liquidity.get(&key).copied().unwrap_or_default();
+                (key, available)
+            })
+            .collect();
+
+        let settled = settle_queue(&queue, &mut liquidity);
+        queue.retain(|(trade_key, _)| !settled.contains(trade_key));
+        let resolved = resolve_gridlock(&queue, &mut liquidity);
+        if settled.is_empty() && resolved.is_empty() {
+            return;
+        }
+
+        for (key, available) in liquidity {
+            self.bank_liquidity.insert(key, available);
+        }
+        self.settle_queued(settled, "Settled from available liquidity");
+        self.settle_queued(resolved, "Settled via gridlock resolution");
+    }
+
+    pub(crate) fn remove_queued_settlement(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+    ) -> Result<(), ContractError> {
+        let trade_key = (partnership_id, trade_id);
+        let settlement = self
+            .take_queued_settlement(&trade_key)
+            .ok_or(ContractError::SettlementNotQueued)?;
+        for payment in settlement.payments.iter() {
+            Self::emit_queue_event(
+                &trade_key,
+                payment,
+                settlement.priority,
+                QueueStatus::Cancelled,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn apply_withdraw_liquidity(
+        &mut self,
+        bank_id: String,
+        ccy: String,
+        amount: U128,
+        receiver_id: AccountId,
+    ) -> Result<(), ContractError> {
+        let token_id = self
+            .currency_tokens
+            .get(&ccy)
+            .ok_or(ContractError::CurrencyNotTokenized)?
+            .token_id
+            .clone();
+        let key = (bank_id, ccy);
+        let available = self.bank_liquidity.get(&key).copied().unwrap_or_default();
+        if amount.0 > available {
+            return Err(ContractError::NotEnoughBalance(amount.0, available));
+        }
+        self.bank_liquidity.insert(key, available - amount.0);
+        self.credit_token_balance(receiver_id, token_id, amount.0);
+
+        Ok(())
+    }
+
+    pub(crate) fn deposit_liquidity(
+        &mut self,
+        bank_id: String,
+        ccy: String,
+        amount: Balance,
+    ) -> Result<(), ContractError> {
+        if !self.banks.contains_key(&bank_id) {
+            return Err(ContractError::BankNotYetExists);
+        }
+        self.assert_not_paused(&[&bank_id], None)?;
+        let key = (bank_id, ccy);
+        let available = self.bank_liquidity.get(&key).copied().unwrap_or_default();
+        self.bank_liquidity.insert(key, available + amount);
+
+        self.process_queue(None);
+
+        Ok(())
+    }
+
+    fn settle_queued(&mut self, trades: Vec<(String, String)>, message: &str) {
+        if trades.is_empty() {
+            return;
+        }
+
+        let payment_status = PaymentStatus::Confirmed(message.to_string());
+        let mut settlements = Vec::with_capacity(trades.len());
+        for trade_key in trades.iter() {
+            let settlement = self.take_queued_settlement(trade_key).unwrap();
+            for payment in settlement.payments.iter() {
+                Self::emit_queue_event(
+                    trade_key,
+                    payment,
+                    settlement.priority,
+                    QueueStatus::Settled(message.to_string()),
+                );
+            }
+            self.settle_exposure(&trade_key.0, &trade_key.1, &payment_status);
+            settlements.push((trade_key.clone(), settlement));
+        }
+
+        self.settle_bank_trades(&trades, message).then(
+            Self::ext(env::current_account_id()).on_settle_queued(settlements, message.to_string()),
+        );
+    }
+
+    /// Reverses the payments of a settlement, which failed, and puts it back at its position in the queue.
+    fn requeue_settlement(&mut self, trade_key: (String, String), settlement: QueuedSettlement) {
+        for payment in settlement.payments.iter() {
+            let leg = &payment.leg;
+            let payer_key = (leg.payer.clone(), leg.ccy.clone());
+            let receiver_key = (leg.receiver.clone(), leg.ccy.clone());
+            let payer = self
+                .bank_liquidity
+                .get(&payer_key)
+                .copied()
+                .unwrap_or_default();
+            let receiver = self
+                .bank_liquidity
+                .get(&receiver_key)
+                .copied()
+                .unwrap_or_default();
+            self.bank_liquidity
+                .insert(payer_key, payer + payment.amount.0);
+            // the receiver might have withdrawn its liquidity in the meantime
+            self.bank_liquidity
+                .insert(receiver_key, receiver.saturating_sub(payment.amount.0));
+        }
+        for payment in settlement.payments.iter() {
+            let reason = self.queue_reason(payment);
+            Self::emit_queue_event(
+                &trade_key,
+                payment,
+                settlement.priority,
+                QueueStatus::Queued(reason),
+            );
+        }
+        self.insert_queued_settlement(trade_key, settlement);
+    }
+
+    /// Queues a settlement at its position in queue order.
+    /// A queued settlement of the same trade is replaced.
+    fn insert_queued_settlement(
+        &mut self,
+        trade_key: (String, String),
+        settlement: QueuedSettlement,
+    ) {
+        let queue_order = settlement.queue_order();
+        if let Some(replaced) = self.settlement_queue.insert(trade_key.clone(), settlement) {
+            self.settlement_queue_order.remove(&replaced.queue_order());
+        }
+        self.settlement_queue_order.insert(queue_order, trade_key);
+    }
+
+    /// Removes a settlement from the queue.
+    fn take_queued_settlement(&mut self, trade_key: &(String, String)) -> Option<QueuedSettlement> {
+        let settlement = self.settlement_queue.remove(trade_key)?;
+        self.settlement_queue_order
+            .remove(&settlement.queue_order());
+        Some(settlement)
+    }
+
+    /// Explains, why a payment has to wait in the queue.
+    fn queue_reason(&self, payment: &QueuedPayment) -> String {
+        let available = self
+            .bank_liquidity
+            .get(&(payment.leg.payer.clone(), payment.leg.ccy.clone()))
+            .copied()
+            .unwrap_or_default();
+        if available < payment.amount.0 {
+            format!(
+                "Insufficient liquidity: {available} of {} available",
+                payment.amount.0
+            )
+        } else {
+            "Waiting for counterparty liquidity".to_string()
+        }
+    }
+
+    fn emit_queue_event(
+        trade_key: &(String, String),
+        payment: &QueuedPayment,
+        priority: u8,
+        status: QueueStatus,
+    ) {
+        let event = RtpEvent::SettlementQueue {
+            partnership_id: trade_key.0.clone(),
+            trade_id: trade_key.1.clone(),
+            bank_id: payment.leg.payer.clone(),
+            ccy: payment.leg.ccy.clone(),
+            amount: payment.amount,
+            priority,
+            status,
+        };
+        event.emit();
+    }
+}
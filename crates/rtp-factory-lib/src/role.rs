@@ -0,0 +1,37 @@
+use crate::{Contract, ContractError, ContractExt};
+use near_sdk::{env, near_bindgen, AccountId};
+use rtp_contract_common::Role;
+
+#[near_bindgen]
+impl Contract {
+    #[handle_result]
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.roles.insert((role, account_id));
+
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn revoke_role(&mut self, role: Role, account_id: AccountId) -> Result<(), ContractError> {
+        self.assert_critical()?;
+        self.roles.remove(&(role, account_id));
+
+        Ok(())
+    }
+
+    pub fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.roles.contains(&(role, account_id))
+    }
+}
+
+impl Contract {
+    /// Ensures, that the caller has been granted given role.
+    pub(crate) fn assert_role(&self, role: Role) -> Result<(), ContractError> {
+        if self.has_role(role, env::predecessor_account_id()) {
+            Ok(())
+        } else {
+            Err(ContractError::MissingRole(role))
+        }
+    }
+}
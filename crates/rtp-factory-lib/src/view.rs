@@ -1,7 +1,12 @@
 use crate::{
     Contract, ContractExt, BANK_DEPOSIT_COVER_ADDITIONAL_BYTES, BANK_DEPOSIT_TO_COVER_GAS,
 };
-use near_sdk::{env, near_bindgen, Balance};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Balance};
+use rtp_contract_common::{
+    net_legs, net_positions, CurrencyToken, Exposure, LifecycleRequest, NdfFixing, NetPosition,
+    NettingCycle, Partnership, PvpEscrow, QueuedSettlement, SettlementInstruction, Trade,
+    MAX_TRADE_BATCH_SIZE,
+};
 
 #[near_bindgen]
 impl Contract {
@@ -15,6 +20,32 @@ impl Contract {
             .collect()
     }
 
+    pub fn is_bank_suspended(&self, bank_id: String) -> bool {
+        self.suspended_banks.contains(&bank_id)
+    }
+
+    /// Trades of a removed bank, which were handed off to the factory before its account was deleted.
+    pub fn get_archived_trades(
+        &self,
+        bank_id: String,
+        skip: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<Trade> {
+        let length = self
+            .bank_archive_lengths
+            .get(&bank_id)
+            .copied()
+            .unwrap_or_default();
+        (skip.unwrap_or_default() as u64..length)
+            .take(limit.unwrap_or(20) as usize)
+            .filter_map(|position| {
+                self.bank_archives
+                    .get(&(bank_id.clone(), position))
+                    .cloned()
+            })
+            .collect()
+    }
+
     pub fn get_bank_id(&self, bank: String) -> String {
         rtp_contract_common::get_bank_id(&bank)
     }
@@ -27,6 +58,158 @@ impl Contract {
         rtp_contract_common::get_partnership_id(bank_a, bank_b)
     }
 
+    pub fn get_partnership(&self, partnership_id: String) -> Option<Partnership> {
+        self.partnerships.get(&partnership_id).cloned()
+    }
+
+    pub fn get_partnerships(
+        &self,
+        skip: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(String, Partnership)> {
+        self.partnerships
+            .iter()
+            .skip(skip.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(20) as usize)
+            .map(|(partnership_id, partnership)| (partnership_id.clone(), partnership.clone()))
+            .collect()
+    }
+
+    pub fn get_exposure(&self, partnership_id: String, ccy: String) -> Option<Exposure> {
+        self.exposures.get(&(partnership_id, ccy)).cloned()
+    }
+
+    /// Exposures of a partnership by currency.
+    pub fn get_exposures(&self, partnership_id: String) -> Vec<(String, Exposure)> {
+        self.exposures
+            .iter()
+            .filter(|((id, _), _)| *id == partnership_id)
+            .map(|((_, ccy), exposure)| (ccy.clone(), exposure.clone()))
+            .collect()
+    }
+
+    /// Share of the exposure limit, which is used by matched and unsettled trades.
+    pub fn get_limit_utilisation(&self, partnership_id: String, ccy: String) -> Option<f64> {
+        self.exposures
+            .get(&(partnership_id, ccy))
+            .and_then(|exposure| exposure.utilisation())
+    }
+
+    /// Net obligations of all matched and unsettled trades of a partnership with given value date,
+    /// which are not yet part of any netting cycle.
+    pub fn get_net_obligations(
+        &self,
+        partnership_id: String,
+        value_date: String,
+    ) -> Vec<SettlementInstruction> {
+        let trades = self.nettable_trades(Some(&partnership_id), Some(&value_date));
+        net_legs(trades.iter().flat_map(|(_, trade)| trade.legs.iter()))
+    }
+
+    /// Net position of every bank per currency over all matched and unsettled trades,
+    /// which are not yet part of any netting cycle.
+    pub fn get_net_positions(&self, value_date: Option<String>) -> Vec<NetPosition> {
+        let trades = self.multilateral_nettable_trades(value_date.as_deref());
+        net_positions(trades.iter().flat_map(|(_, trade)| trade.legs.iter()))
+            .into_iter()
+            .flat_map(|(ccy, positions)| {
+                positions
+                    .into_iter()
+                    .map(move |(bank_id, amount)| NetPosition {
+                        bank_id,
+                        ccy: ccy.clone(),
+                        amount,
+                    })
+            })
+            .collect()
+    }
+
+    /// Settlement instructions of a multilateral netting run over all banks.
+    pub fn get_multilateral_net_obligations(
+        &self,
+        value_date: Option<String>,
+    ) -> Vec<SettlementInstruction> {
+        let trades = self.multilateral_nettable_trades(value_date.as_deref());
+        net_legs(trades.iter().flat_map(|(_, trade)| trade.legs.iter()))
+    }
+
+    /// Net cash-settlement of an NDF, as soon as it has been fixed.
+    pub fn get_ndf_fixing(&self, partnership_id: String, trade_id: String) -> Option<NdfFixing> {
+        self.partnership_trades
+            .get(&(partnership_id, trade_id))
+            .and_then(|trade| trade.ndf.as_ref())
+            .and_then(|ndf| ndf.fixing.clone())
+    }
+
+    pub fn get_lifecycle_request(
+        &self,
+        partnership_id: String,
+        trade_id: String,
+    ) -> Option<LifecycleRequest> {
+        self.lifecycle_requests
+            .get(&(partnership_id, trade_id))
+            .cloned()
+    }
+
+    pub fn get_netting_cycle(&self, cycle_id: u64) -> Option<NettingCycle> {
+        self.netting_cycles.get(&cycle_id).cloned()
+    }
+
+    pub fn get_currency_token(&self, ccy: String) -> Option<CurrencyToken> {
+        self.currency_tokens.get(&ccy).cloned()
+    }
+
+    pub fn get_settlement_account(&self, bank_id: String) -> Option<AccountId> {
+        self.settlement_accounts.get(&bank_id).cloned()
+    }
+
+    pub fn get_pvp_escrow(&self, partnership_id: String, trade_id: String) -> Option<PvpEscrow> {
+        self.pvp_escrows.get(&(partnership_id, trade_id)).cloned()
+    }
+
+    /// Tokens, which can be withdrawn by given account.
+    pub fn get_token_balance(&self, account_id: AccountId, token_id: AccountId) -> U128 {
+        U128(
+            self.token_balances
+                .get(&(account_id, token_id))
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn get_liquidity(&self, bank_id: String, ccy: String) -> U128 {
+        U128(
+            self.bank_liquidity
+                .get(&(bank_id, ccy))
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Queued settlements with a payment of given bank in given currency, in queue order.
+    pub fn get_settlement_queue(
+        &self,
+        bank_id: String,
+        ccy: String,
+    ) -> Vec<((String, String), QueuedSettlement)> {
+        self.settlement_queue_order
+            .values()
+            .map(|trade_key| (trade_key, &self.settlement_queue[trade_key]))
+            .filter(|(_, settlement)| {
+                settlement
+                    .payments
+                    .iter()
+                    .any(|payment| payment.leg.payer == bank_id && payment.leg.ccy == ccy)
+            })
+            .map(|(trade_key, settlement)| (trade_key.clone(), settlement.clone()))
+            .collect()
+    }
+
+    /// Maximum number of trades, which can be submitted via `perform_trades` at once.
+    pub fn get_max_trade_batch_size(&self) -> u32 {
+        MAX_TRADE_BATCH_SIZE
+    }
+
     pub fn get_bank_storage_cost(&self) -> Balance {
         let code = self.contract_code.get();
         let code_len: usize = code.len();
@@ -1,13 +1,22 @@
-use crate::{ContractError, StorageKey};
+use crate::{
+    factory, migration::LegacyContract, ContractError, StorageKey, AUDIT_ENTRY_STORAGE_BYTES,
+    INITIAL_STORAGE_BYTES, TRADE_STORAGE_RESERVE_BYTES,
+};
+use near_contract_standards::storage_management::StorageBalance;
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    env, near_bindgen,
-    store::UnorderedMap,
-    AccountId, PanicOnDefault, Promise,
+    env,
+    json_types::U128,
+    near_bindgen,
+    store::{LookupMap, UnorderedMap, UnorderedSet},
+    AccountId, Balance, PanicOnDefault, Promise, PromiseOrValue, PublicKey,
 };
 use rtp_contract_common::{
-    get_bank_id, get_partnership_id, MatchingStatus, PaymentConfirmation, PaymentStatus, Payments,
-    RtpEvent, Trade, TradeDetails,
+    close_out_trade_id, compliance_hold, get_bank_id, get_partnership_id, permission_violation,
+    ApprovalStatus, AuditEntry, BlockTime, DeskPermissions, LifecycleAction, MatchingStatus,
+    NdfFixing, OperatorKey, PartnershipTerms, PaymentConfirmation, PaymentStatus, Payments,
+    Product, RtpEvent, SwapLeg, Trade, TradeDetails, TradeLatency, TradeState, TradeSubmission,
+    TraderPermissions, OPERATOR_KEY_METHODS,
 };
 
 #[near_bindgen]
@@ -16,48 +25,534 @@ pub struct Contract {
     factory: AccountId,
     bank: String,
     trades: UnorderedMap<String, Trade>,
+    /// Account, which maintains the allowed counterparties.
+    admin: AccountId,
+    /// Names of all counterparties, which the bank trades with.
+    /// The bank trades with any counterparty, as long as it is empty.
+    allowed_counterparties: UnorderedSet<String>,
+    /// Notional amounts per currency, above which trades require four-eyes approval.
+    approval_thresholds: UnorderedMap<String, f64>,
+    /// Operator accounts, which are authorised to approve trades.
+    approvers: UnorderedSet<AccountId>,
+    /// Permissions of traders by their trader ID.
+    traders: UnorderedMap<String, TraderPermissions>,
+    /// Permissions of desks by their account.
+    desks: UnorderedMap<String, DeskPermissions>,
+    /// Whether new trades are validated against the permissions of their trader and desk.
+    permissions_enforced: bool,
+    /// Append-only audit trail of every trade by trade ID and position in the trail.
+    trade_history: LookupMap<(String, u32), AuditEntry>,
+    /// Number of entries in the audit trail of every trade.
+    trade_history_lengths: LookupMap<String, u32>,
+    /// Storage usage, which is covered by the deposit attached at creation of the bank.
+    storage_baseline: u64,
+    /// Deposit, against which all storage beyond the baseline is charged.
+    storage_balance: Balance,
+    /// Whether trading, status changes and payment confirmations are halted.
+    paused: bool,
+    /// Function call access key of the bank's operator.
+    operator_key: Option<PublicKey>,
 }
 
 #[near_bindgen]
 impl Contract {
+    /// The admin defaults to the factory, if not set.
     #[init]
-    pub fn new(factory: AccountId, bank: String) -> Self {
+    pub fn new(
+        factory: AccountId,
+        bank: String,
+        admin: Option<AccountId>,
+        operator_key: Option<PublicKey>,
+    ) -> Self {
         Self {
+            admin: admin.unwrap_or_else(|| factory.clone()),
             factory,
             bank,
             trades: UnorderedMap::new(StorageKey::Trades),
+            allowed_counterparties: UnorderedSet::new(StorageKey::AllowedCounterparties),
+            approval_thresholds: UnorderedMap::new(StorageKey::ApprovalThresholds),
+            approvers: UnorderedSet::new(StorageKey::Approvers),
+            traders: UnorderedMap::new(StorageKey::Traders),
+            desks: UnorderedMap::new(StorageKey::Desks),
+            permissions_enforced: false,
+            trade_history: LookupMap::new(StorageKey::TradeHistory),
+            trade_history_lengths: LookupMap::new(StorageKey::TradeHistoryLengths),
+            storage_baseline: env::storage_usage() + INITIAL_STORAGE_BYTES,
+            storage_balance: 0,
+            paused: false,
+            operator_key,
         }
     }
 
+    /// Migrate the state of a bank contract, which only tracked its trades.
+    /// The factory remains the admin and the allow-list stays empty, so all counterparties are allowed.
+    #[private]
+    #[init(ignore_state)]
+    #[handle_result]
+    pub fn migrate() -> Result<Self, ContractError> {
+        let mut legacy: LegacyContract = env::state_read()
+            .ok_or_else(|| ContractError::MigrationFailed("No legacy state found".to_string()))?;
+        let trades: Vec<(String, Trade)> = legacy
+            .trades
+            .drain()
+            .map(|(trade_id, trade)| (trade_id, trade.into()))
+            .collect();
+        legacy.trades.flush();
+
+        let mut contract = Self::new(legacy.factory, legacy.bank, None, None);
+        contract.trades.extend(trades);
+        contract.trades.flush();
+        contract.storage_baseline = env::storage_usage() + INITIAL_STORAGE_BYTES;
+
+        Ok(contract)
+    }
+
+    /// Allow or disallow trading with given counterparty.
     #[handle_result]
-    pub fn perform_trade(&mut self, trade_details: TradeDetails) -> Result<(), ContractError> {
+    pub fn set_counterparty_allowed(
+        &mut self,
+        counterparty: String,
+        allowed: bool,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        if allowed {
+            self.allowed_counterparties.insert(counterparty);
+        } else {
+            self.allowed_counterparties.remove(&counterparty);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_allowed_counterparties(&self) -> Vec<String> {
+        self.allowed_counterparties.iter().cloned().collect()
+    }
+
+    pub fn get_admin(&self) -> AccountId {
+        self.admin.clone()
+    }
+
+    /// Propose a partnership with given terms to the counterparty on behalf of the bank.
+    #[handle_result]
+    pub fn propose_partnership(
+        &mut self,
+        counterparty: String,
+        terms: PartnershipTerms,
+    ) -> Result<Promise, ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        Ok(factory::ext(self.factory.clone())
+            .with_unused_gas_weight(1)
+            .propose_partnership(get_bank_id(&self.bank), counterparty, terms))
+    }
+
+    /// Accept the partnership terms, which have been proposed by the counterparty, on behalf of the bank.
+    #[handle_result]
+    pub fn accept_partnership(&mut self, counterparty: String) -> Result<Promise, ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        Ok(factory::ext(self.factory.clone())
+            .with_unused_gas_weight(1)
+            .accept_partnership(get_bank_id(&self.bank), counterparty))
+    }
+
+    /// Request to roll or terminate a trade of the partnership on behalf of the bank.
+    #[handle_result]
+    pub fn request_lifecycle(
+        &mut self,
+        partnership_id: String,
+        trade_id: String,
+        action: LifecycleAction,
+    ) -> Result<Promise, ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        Ok(factory::ext(self.factory.clone())
+            .with_unused_gas_weight(1)
+            .request_lifecycle(get_bank_id(&self.bank), partnership_id, trade_id, action))
+    }
+
+    /// Top up the storage balance of the bank with the attached deposit.
+    #[payable]
+    pub fn storage_deposit(&mut self) -> StorageBalance {
+        self.storage_balance += env::attached_deposit();
+        self.storage_balance_of()
+    }
+
+    /// Withdraw the available storage balance to the admin. Withdraws everything, if `amount` is not set.
+    #[handle_result]
+    pub fn storage_withdraw(
+        &mut self,
+        amount: Option<U128>,
+    ) -> Result<StorageBalance, ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        let available = self.storage_balance_of().available.0;
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        if amount > available {
+            return Err(ContractError::NotEnoughStorageBalance(amount, available));
+        }
+        self.storage_balance -= amount;
+        if amount > 0 {
+            Promise::new(self.admin.clone()).transfer(amount);
+        }
+
+        Ok(self.storage_balance_of())
+    }
+
+    /// Total deposit and the part of it, which is not used by storage yet.
+    pub fn storage_balance_of(&self) -> StorageBalance {
+        let used = self.get_storage_usage() as Balance * env::storage_byte_cost();
+        StorageBalance {
+            total: U128(self.storage_balance),
+            available: U128(self.storage_balance.saturating_sub(used)),
+        }
+    }
+
+    /// Storage in bytes, which is charged against the storage balance.
+    pub fn get_storage_usage(&self) -> u64 {
+        env::storage_usage().saturating_sub(self.storage_baseline)
+    }
+
+    /// Deposit, which is required before a new trade can be accepted.
+    pub fn get_storage_top_up(&self) -> U128 {
+        let required = (self.get_storage_usage() + TRADE_STORAGE_RESERVE_BYTES) as Balance
+            * env::storage_byte_cost();
+        U128(required.saturating_sub(self.storage_balance))
+    }
+
+    /// Set the notional amount of a currency, above which trades require four-eyes approval.
+    /// Removes the threshold, if `threshold` is not set.
+    #[handle_result]
+    pub fn set_approval_threshold(
+        &mut self,
+        ccy: String,
+        threshold: Option<f64>,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        match threshold {
+            Some(threshold) if !threshold.is_finite() || threshold < 0. => {
+                return Err(ContractError::InvalidApprovalThreshold);
+            }
+            Some(threshold) => {
+                self.approval_thresholds.insert(ccy, threshold);
+            }
+            None => {
+                self.approval_thresholds.remove(&ccy);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_approval_threshold(&self, ccy: String) -> Option<f64> {
+        self.approval_thresholds.get(&ccy).copied()
+    }
+
+    /// Authorise or deauthorise an operator account to approve trades.
+    #[handle_result]
+    pub fn set_approver(
+        &mut self,
+        account_id: AccountId,
+        authorised: bool,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        if authorised {
+            self.approvers.insert(account_id);
+        } else {
+            self.approvers.remove(&account_id);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_approvers(&self) -> Vec<AccountId> {
+        self.approvers.iter().cloned().collect()
+    }
+
+    /// Set the permissions of a trader. Removes the trader, if `permissions` are not set.
+    #[handle_result]
+    pub fn set_trader_permissions(
+        &mut self,
+        trader_id: String,
+        permissions: Option<TraderPermissions>,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        if let Some(permissions) = &permissions {
+            self.traders.insert(trader_id.clone(), permissions.clone());
+        } else {
+            self.traders.remove(&trader_id);
+        }
+
+        let event = RtpEvent::SetTraderPermissions {
+            bank_id: get_bank_id(&self.bank),
+            trader_id,
+            permissions,
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    pub fn get_trader_permissions(&self, trader_id: String) -> Option<TraderPermissions> {
+        self.traders.get(&trader_id).cloned()
+    }
+
+    /// Set the permissions of a desk. Removes the desk, if `permissions` are not set.
+    #[handle_result]
+    pub fn set_desk_permissions(
+        &mut self,
+        desk: String,
+        permissions: Option<DeskPermissions>,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        if let Some(permissions) = &permissions {
+            self.desks.insert(desk.clone(), permissions.clone());
+        } else {
+            self.desks.remove(&desk);
+        }
+
+        let event = RtpEvent::SetDeskPermissions {
+            bank_id: get_bank_id(&self.bank),
+            desk,
+            permissions,
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    pub fn get_desk_permissions(&self, desk: String) -> Option<DeskPermissions> {
+        self.desks.get(&desk).cloned()
+    }
+
+    /// Enable or disable the validation of new trades against the trader and desk permissions.
+    #[handle_result]
+    pub fn set_permissions_enforced(&mut self, enforced: bool) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        self.permissions_enforced = enforced;
+
+        let event = RtpEvent::EnforcePermissions {
+            bank_id: get_bank_id(&self.bank),
+            enforced,
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    pub fn are_permissions_enforced(&self) -> bool {
+        self.permissions_enforced
+    }
+
+    /// Halt or resume trading, status changes and payment confirmations of the bank.
+    #[handle_result]
+    pub fn set_paused(&mut self, paused: bool) -> Result<(), ContractError> {
         if env::predecessor_account_id() != self.factory {
             return Err(ContractError::NotFactory);
         }
 
-        self.trades.insert(
-            trade_details.trade_id.clone(),
-            Trade {
-                bank: self.bank.clone(),
-                trade_details: trade_details.clone(),
-                matching_status: MatchingStatus::Pending,
-                payment_status: PaymentStatus::Pending,
-                payments: Payments::default(),
-            },
+        self.paused = paused;
+
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Replace the access key of the bank's operator, which can only call `perform_trade`
+    /// and `confirm_payment` of the bank contract.
+    #[handle_result]
+    pub fn rotate_operator_key(&mut self, operator_key: OperatorKey) -> Result<(), ContractError> {
+        self.assert_factory_or_admin()?;
+
+        let account_id = env::current_account_id();
+        let mut promise = Promise::new(account_id.clone());
+        if let Some(old_key) = self.operator_key.take() {
+            promise = promise.delete_key(old_key);
+        }
+        promise.add_access_key(
+            operator_key.public_key.clone(),
+            operator_key.allowance.0,
+            account_id,
+            OPERATOR_KEY_METHODS.to_string(),
         );
+        self.operator_key = Some(operator_key.public_key.clone());
+
+        let event = RtpEvent::RotateOperatorKey {
+            bank_id: get_bank_id(&self.bank),
+            public_key: String::from(&operator_key.public_key),
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn revoke_operator_key(&mut self) -> Result<(), ContractError> {
+        self.assert_factory_or_admin()?;
+
+        let public_key = self
+            .operator_key
+            .take()
+            .ok_or(ContractError::NoOperatorKey)?;
+        Promise::new(env::current_account_id()).delete_key(public_key.clone());
+
+        let event = RtpEvent::RevokeOperatorKey {
+            bank_id: get_bank_id(&self.bank),
+            public_key: String::from(&public_key),
+        };
+        event.emit();
+
+        Ok(())
+    }
+
+    pub fn get_operator_key(&self) -> Option<PublicKey> {
+        self.operator_key.clone()
+    }
+
+    /// Approve a trade awaiting approval, which releases it for matching.
+    #[handle_result]
+    pub fn approve_trade(&mut self, trade_id: String) -> Result<(), ContractError> {
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+        let approver = self.assert_approver(&trade_id)?;
+
+        let trade = self.trades.get_mut(&trade_id).unwrap();
+        let old_state = TradeState::from(&*trade);
+        trade.approval_status = Some(ApprovalStatus::Approved {
+            approver: approver.to_string(),
+        });
+        let trade_details = trade.trade_details.clone();
+        let compliance_hold = trade.compliance_hold.clone();
+        self.record_transition(
+            &trade_id,
+            Some(old_state),
+            format!("Approved by {approver}"),
+        )?;
+        self.send_trade(trade_details, compliance_hold);
+
+        Ok(())
+    }
+
+    /// Reject a trade awaiting approval. The trade is never released for matching
+    /// and the factory releases its exposure.
+    #[handle_result]
+    pub fn reject_trade(&mut self, trade_id: String) -> Result<(), ContractError> {
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+        let approver = self.assert_approver(&trade_id)?;
+
+        let trade = self.trades.get_mut(&trade_id).unwrap();
+        let old_state = TradeState::from(&*trade);
+        trade.approval_status = Some(ApprovalStatus::Rejected {
+            approver: approver.to_string(),
+        });
+        trade.matching_status = MatchingStatus::Rejected("Rejected by approver".to_string());
         let partnership_id =
-            get_partnership_id(self.bank.clone(), trade_details.counterparty.clone());
+            get_partnership_id(self.bank.clone(), trade.trade_details.counterparty.clone());
+        self.record_transition(
+            &trade_id,
+            Some(old_state),
+            format!("Rejected by {approver}"),
+        )?;
 
-        let event: RtpEvent = RtpEvent::SendTrade {
-            partnership_id,
+        let event = RtpEvent::RejectTrade {
+            partnership_id: partnership_id.clone(),
             bank_id: get_bank_id(&self.bank),
-            trade: trade_details,
+            trade_id: trade_id.clone(),
+            approver: approver.to_string(),
         };
         event.emit();
 
+        factory::ext(self.factory.clone())
+            .with_unused_gas_weight(1)
+            .release_trade(get_bank_id(&self.bank), partnership_id, trade_id);
+
         Ok(())
     }
 
+    /// Store a new trade. Trades, which fail the compliance policy, are held back from matching.
+    /// Trades submitted with the operator key are forwarded to the factory for validation,
+    /// which then stores them with their screening result, so `screened` is not set by operators.
+    #[handle_result]
+    pub fn perform_trade(
+        &mut self,
+        trade_details: TradeDetails,
+        screened: Option<bool>,
+    ) -> Result<PromiseOrValue<()>, ContractError> {
+        if self.is_operator() {
+            return Ok(factory::ext(self.factory.clone())
+                .with_unused_gas_weight(1)
+                .perform_trade(get_bank_id(&self.bank), trade_details)
+                .into());
+        }
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        self.store_trade(trade_details, screened.unwrap_or_default())?;
+
+        Ok(PromiseOrValue::Value(()))
+    }
+
+    /// Store a batch of trades with their screening results.
+    /// Every trade is stored or rejected on its own and emits its own `send_trade` event.
+    #[handle_result]
+    pub fn perform_trades(
+        &mut self,
+        trades: Vec<(TradeDetails, bool)>,
+    ) -> Result<Vec<TradeSubmission>, ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        Ok(trades
+            .into_iter()
+            .map(|(trade_details, screened)| TradeSubmission {
+                trade_id: trade_details.trade_id.clone(),
+                error: self
+                    .store_trade(trade_details, screened)
+                    .err()
+                    .map(|err| err.to_string()),
+            })
+            .collect())
+    }
+
     #[handle_result]
     pub fn set_matching_status(
         &mut self,
@@ -67,46 +562,116 @@ impl Contract {
         if env::predecessor_account_id() != self.factory {
             return Err(ContractError::NotFactory);
         }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
 
-        let trade = self
-            .trades
-            .get_mut(&trade_id)
-            .ok_or(ContractError::InvalidTradeId)?;
-        trade.matching_status = matching_status;
+        self.update_matching_status(trade_id, matching_status)
+    }
 
-        Ok(())
+    /// Update the matching status of several trades at once.
+    /// Returns the error of every update, which failed, in the order of the updates.
+    #[handle_result]
+    pub fn set_matching_statuses(
+        &mut self,
+        updates: Vec<(String, MatchingStatus)>,
+    ) -> Result<Vec<Option<String>>, ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        Ok(updates
+            .into_iter()
+            .map(|(trade_id, matching_status)| {
+                self.update_matching_status(trade_id, matching_status)
+                    .err()
+                    .map(|err| err.to_string())
+            })
+            .collect())
     }
 
+    /// Payments confirmed with the operator key are forwarded to the factory,
+    /// which confirms them with both counterparties.
+    /// Operators can only confirm the receipt of a payment, i.e. the credit of their bank.
     #[handle_result]
     pub fn confirm_payment(
         &mut self,
         trade_id: String,
         confirmation: PaymentConfirmation,
-    ) -> Result<(), ContractError> {
+    ) -> Result<PromiseOrValue<()>, ContractError> {
+        if self.is_operator() {
+            let trade = self
+                .trades
+                .get(&trade_id)
+                .ok_or(ContractError::InvalidTradeId)?;
+            if !matches!(confirmation, PaymentConfirmation::Credit) {
+                return Err(ContractError::NotCreditor);
+            }
+            return Ok(factory::ext(self.factory.clone())
+                .with_unused_gas_weight(1)
+                .confirm_payment(
+                    get_bank_id(&self.bank),
+                    get_bank_id(&trade.trade_details.counterparty),
+                    trade_id,
+                )
+                .into());
+        }
         if env::predecessor_account_id() != self.factory {
             return Err(ContractError::NotFactory);
         }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
 
         let trade = self
             .trades
             .get_mut(&trade_id)
             .ok_or(ContractError::InvalidTradeId)?;
+        if trade.trade_details.product == Product::Ndf && trade.ndf_fixing.is_none() {
+            return Err(ContractError::NdfNotYetFixed);
+        }
+        let old_state = TradeState::from(&*trade);
         match confirmation {
             PaymentConfirmation::Credit => trade.payments.credit = true,
             PaymentConfirmation::Debit => trade.payments.debit = true,
         }
         let partnership_id =
             get_partnership_id(self.bank.clone(), trade.trade_details.counterparty.clone());
+        self.record_transition(
+            &trade_id,
+            Some(old_state),
+            format!("{confirmation:?} payment confirmed"),
+        )?;
 
         let event = RtpEvent::ConfirmPayment {
             partnership_id,
             bank_id: get_bank_id(&self.bank),
-            trade_id: trade.trade_details.trade_id.clone(),
+            trade_id,
             confirmation,
+            block_time: BlockTime::now(),
         };
         event.emit();
 
-        Ok(())
+        Ok(PromiseOrValue::Value(()))
+    }
+
+    /// Confirm a net payment of a netting cycle, which the bank either made or received.
+    #[handle_result]
+    pub fn confirm_net_payment(
+        &mut self,
+        cycle_id: u64,
+        instruction_id: u32,
+    ) -> Result<Promise, ContractError> {
+        if env::predecessor_account_id() != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        Ok(factory::ext(self.factory.clone())
+            .with_unused_gas_weight(1)
+            .confirm_net_payment(get_bank_id(&self.bank), cycle_id, instruction_id))
     }
 
     #[handle_result]
@@ -118,17 +683,184 @@ impl Contract {
         if env::predecessor_account_id() != self.factory {
             return Err(ContractError::NotFactory);
         }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        self.update_payment_status(trade_id, payment_status)
+    }
+
+    /// Update the payment status of several trades at once.
+    /// Returns the error of every update, which failed, in the order of the updates.
+    #[handle_result]
+    pub fn set_payment_statuses(
+        &mut self,
+        updates: Vec<(String, PaymentStatus)>,
+    ) -> Result<Vec<Option<String>>, ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        Ok(updates
+            .into_iter()
+            .map(|(trade_id, payment_status)| {
+                self.update_payment_status(trade_id, payment_status)
+                    .err()
+                    .map(|err| err.to_string())
+            })
+            .collect())
+    }
+
+    /// Mark trades as fully paid, e.g. after they have been settled via net payments.
+    #[handle_result]
+    pub fn settle_trades(
+        &mut self,
+        trade_ids: Vec<String>,
+        message: String,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+
+        for trade_id in trade_ids.iter() {
+            let trade = self
+                .trades
+                .get(trade_id)
+                .ok_or(ContractError::InvalidTradeId)?;
+            if !matches!(trade.matching_status, MatchingStatus::Confirmed(_)) {
+                return Err(ContractError::TradeMatchUnconfirmed);
+            }
+        }
+        for trade_id in trade_ids {
+            let trade = self.trades.get_mut(&trade_id).unwrap();
+            let old_state = TradeState::from(&*trade);
+            trade.payments.credit = true;
+            trade.payments.debit = true;
+            trade.payment_status = PaymentStatus::Confirmed(message.clone());
+            trade.paid_at = Some(BlockTime::now());
+            self.record_transition(&trade_id, Some(old_state), message.clone())?;
+            self.complete_swap(&trade_id);
+        }
+
+        Ok(())
+    }
+
+    /// Store the net cash-settlement of an NDF after its fixing.
+    #[handle_result]
+    pub fn set_ndf_fixing(
+        &mut self,
+        trade_id: String,
+        fixing: NdfFixing,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
 
         let trade = self
             .trades
             .get_mut(&trade_id)
             .ok_or(ContractError::InvalidTradeId)?;
-        if let MatchingStatus::Confirmed(_) = trade.matching_status {
-            trade.payment_status = payment_status;
-            Ok(())
-        } else {
-            Err(ContractError::TradeMatchUnconfirmed)
+        let old_state = TradeState::from(&*trade);
+        trade.ndf_fixing = Some(fixing);
+        self.record_transition(&trade_id, Some(old_state), "NDF fixed".to_string())?;
+
+        Ok(())
+    }
+
+    /// Close the trade after both counterparties agreed to roll or terminate it.
+    /// Rolling a trade creates a new matched trade, which references the original one via `source_trade_id`.
+    /// Terminating a trade likewise creates a matched close-out trade, which only pays the close-out amount.
+    #[handle_result]
+    pub fn apply_lifecycle(
+        &mut self,
+        trade_id: String,
+        action: LifecycleAction,
+    ) -> Result<(), ContractError> {
+        if env::predecessor_account_id() != self.factory {
+            return Err(ContractError::NotFactory);
+        }
+        if self.paused {
+            return Err(ContractError::Paused);
         }
+        // the replacing trade is charged like any new trade
+        self.assert_storage_covered(TRADE_STORAGE_RESERVE_BYTES)?;
+
+        let trade = self
+            .trades
+            .get_mut(&trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        let old_state = TradeState::from(&*trade);
+        trade.lifecycle = Some(action.clone());
+        let trade_details = trade.trade_details.clone();
+        let message = match &action {
+            LifecycleAction::Roll { new_trade_id, .. } => format!("Rolled into {new_trade_id}"),
+            LifecycleAction::Terminate { .. } => {
+                format!(
+                    "Terminated against close-out {}",
+                    close_out_trade_id(&trade_id)
+                )
+            }
+        };
+        self.record_transition(&trade_id, Some(old_state), message)?;
+
+        // the replacing trade is matched and settled like any other trade
+        let (trade_details, message) = match action {
+            LifecycleAction::Roll {
+                new_trade_id,
+                value_date,
+                price,
+            } => (
+                TradeDetails {
+                    trade_id: new_trade_id,
+                    price,
+                    ccy1_value_date: value_date,
+                    source_trade_id: trade_id.clone(),
+                    ..trade_details
+                },
+                format!("Rolled from {trade_id}"),
+            ),
+            LifecycleAction::Terminate {
+                close_out,
+                value_date,
+            } => (
+                TradeDetails {
+                    trade_id: close_out_trade_id(&trade_id),
+                    ccy: close_out.ccy,
+                    notional_amount: close_out.amount as f32,
+                    ccy1_value_date: value_date,
+                    source_trade_id: trade_id.clone(),
+                    ..trade_details
+                },
+                format!("Close-out of {trade_id}"),
+            ),
+        };
+        let new_trade_id = trade_details.trade_id.clone();
+        self.trades.insert(
+            new_trade_id.clone(),
+            Trade {
+                bank: self.bank.clone(),
+                trade_details,
+                matching_status: MatchingStatus::Confirmed(message.clone()),
+                payment_status: PaymentStatus::Pending,
+                payments: Payments::default(),
+                ndf_fixing: None,
+                lifecycle: None,
+                compliance_hold: None,
+                approval_status: None,
+                submitted_at: BlockTime::now(),
+                matched_at: Some(BlockTime::now()),
+                paid_at: None,
+            },
+        );
+        self.record_transition(&new_trade_id, None, message)?;
+
+        Ok(())
     }
 
     #[handle_result]
@@ -152,4 +884,315 @@ impl Contract {
             .ok_or(ContractError::InvalidTradeId)?;
         Ok(trade.clone())
     }
+
+    /// Trades of the bank, e.g. to archive them in batches before the bank is removed.
+    pub fn get_trades(&self, skip: Option<u32>, limit: Option<u32>) -> Vec<Trade> {
+        self.trades
+            .values()
+            .skip(skip.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(20) as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Latencies of matching and payment of a trade based on block timestamps.
+    #[handle_result]
+    pub fn get_trade_latency(&self, trade_id: String) -> Result<TradeLatency, ContractError> {
+        self.trades
+            .get(&trade_id)
+            .map(Trade::latency)
+            .ok_or(ContractError::InvalidTradeId)
+    }
+
+    /// All transitions of a trade in the order, in which they happened.
+    #[handle_result]
+    pub fn get_trade_history(&self, trade_id: String) -> Result<Vec<AuditEntry>, ContractError> {
+        let length = *self
+            .trade_history_lengths
+            .get(&trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        Ok((0..length)
+            .filter_map(|position| self.trade_history.get(&(trade_id.clone(), position)))
+            .cloned()
+            .collect())
+    }
+
+    /// Whether both legs of the swap, which the trade is part of, have been paid.
+    #[handle_result]
+    pub fn is_swap_complete(&self, trade_id: String) -> Result<bool, ContractError> {
+        let trade = self
+            .trades
+            .get(&trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        if trade.trade_details.product != Product::Swap {
+            return Err(ContractError::NotASwap);
+        }
+        Ok(trade.is_paid()
+            && self
+                .trades
+                .get(&trade.trade_details.secondary_trade_id)
+                .map(|linked_trade| linked_trade.is_paid())
+                .unwrap_or_default())
+    }
+}
+
+impl Contract {
+    /// Completes the swap, as soon as the trade and its linked leg have both been paid.
+    fn complete_swap(&self, trade_id: &str) {
+        let Ok(true) = self.is_swap_complete(trade_id.to_string()) else {
+            return;
+        };
+        let trade_details = &self.trades.get(trade_id).unwrap().trade_details;
+        let (near_trade_id, far_trade_id) = match trade_details.swap_leg {
+            Some(SwapLeg::Far) => (
+                trade_details.secondary_trade_id.clone(),
+                trade_details.trade_id.clone(),
+            ),
+            _ => (
+                trade_details.trade_id.clone(),
+                trade_details.secondary_trade_id.clone(),
+            ),
+        };
+
+        let event = RtpEvent::CompleteSwap {
+            partnership_id: get_partnership_id(
+                self.bank.clone(),
+                trade_details.counterparty.clone(),
+            ),
+            bank_id: get_bank_id(&self.bank),
+            near_trade_id,
+            far_trade_id,
+        };
+        event.emit();
+    }
+
+    /// Emits the trade for matching, followed by its compliance hold, if any.
+    fn send_trade(&self, trade_details: TradeDetails, compliance_hold: Option<String>) {
+        let block_time = self.trades[&trade_details.trade_id].submitted_at.clone();
+        let partnership_id =
+            get_partnership_id(self.bank.clone(), trade_details.counterparty.clone());
+        let trade_id = trade_details.trade_id.clone();
+
+        let event: RtpEvent = RtpEvent::SendTrade {
+            partnership_id: partnership_id.clone(),
+            bank_id: get_bank_id(&self.bank),
+            trade: trade_details,
+            block_time,
+        };
+        event.emit();
+
+        if let Some(reason) = compliance_hold {
+            let event = RtpEvent::ComplianceHold {
+                partnership_id,
+                bank_id: get_bank_id(&self.bank),
+                trade_id,
+                reason,
+            };
+            event.emit();
+        }
+    }
+
+    fn update_matching_status(
+        &mut self,
+        trade_id: String,
+        matching_status: MatchingStatus,
+    ) -> Result<(), ContractError> {
+        // checked before the update, so that a failed update within a batch leaves the trade untouched
+        self.assert_storage_covered(AUDIT_ENTRY_STORAGE_BYTES)?;
+        let trade = self
+            .trades
+            .get_mut(&trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        if let (MatchingStatus::Confirmed(_), Some(reason)) =
+            (&matching_status, &trade.compliance_hold)
+        {
+            return Err(ContractError::ComplianceHold(reason.clone()));
+        }
+        if let (
+            MatchingStatus::Confirmed(_),
+            Some(ApprovalStatus::AwaitingApproval { .. } | ApprovalStatus::Rejected { .. }),
+        ) = (&matching_status, &trade.approval_status)
+        {
+            return Err(ContractError::TradeNotApproved);
+        }
+        let old_state = TradeState::from(&*trade);
+        if let MatchingStatus::Confirmed(_) = matching_status {
+            trade.matched_at = Some(BlockTime::now());
+        }
+        trade.matching_status = matching_status;
+        self.record_transition(
+            &trade_id,
+            Some(old_state),
+            "Matching status updated".to_string(),
+        )?;
+
+        Ok(())
+    }
+
+    fn update_payment_status(
+        &mut self,
+        trade_id: String,
+        payment_status: PaymentStatus,
+    ) -> Result<(), ContractError> {
+        // checked before the update, so that a failed update within a batch leaves the trade untouched
+        self.assert_storage_covered(AUDIT_ENTRY_STORAGE_BYTES)?;
+        let trade = self
+            .trades
+            .get_mut(&trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        if let MatchingStatus::Confirmed(_) = trade.matching_status {
+            let old_state = TradeState::from(&*trade);
+            if let PaymentStatus::Confirmed(_) = payment_status {
+                trade.paid_at = Some(BlockTime::now());
+            }
+            trade.payment_status = payment_status;
+            self.record_transition(
+                &trade_id,
+                Some(old_state),
+                "Payment status updated".to_string(),
+            )?;
+            self.complete_swap(&trade_id);
+            Ok(())
+        } else {
+            Err(ContractError::TradeMatchUnconfirmed)
+        }
+    }
+
+    /// Validates a new trade against the bank's policies and stores it.
+    fn store_trade(
+        &mut self,
+        trade_details: TradeDetails,
+        screened: bool,
+    ) -> Result<(), ContractError> {
+        if !self.allowed_counterparties.is_empty()
+            && !self
+                .allowed_counterparties
+                .contains(&trade_details.counterparty)
+        {
+            return Err(ContractError::CounterpartyNotAllowed);
+        }
+        self.assert_storage_covered(TRADE_STORAGE_RESERVE_BYTES)?;
+        if self.permissions_enforced {
+            if let Some(reason) = permission_violation(
+                &trade_details,
+                self.traders.get(&trade_details.trader_id),
+                self.desks.get(&trade_details.account),
+            ) {
+                return Err(ContractError::TradeNotPermitted(reason));
+            }
+        }
+
+        let compliance_hold = compliance_hold(&trade_details, screened);
+        let approval_status = self
+            .approval_thresholds
+            .get(&trade_details.ccy)
+            .filter(|threshold| trade_details.notional_amount as f64 > **threshold)
+            .map(|_| ApprovalStatus::AwaitingApproval {
+                submitted_by: env::signer_account_id().to_string(),
+            });
+        let is_awaiting_approval = approval_status.is_some();
+        self.trades.insert(
+            trade_details.trade_id.clone(),
+            Trade {
+                bank: self.bank.clone(),
+                trade_details: trade_details.clone(),
+                matching_status: MatchingStatus::Pending,
+                payment_status: PaymentStatus::Pending,
+                payments: Payments::default(),
+                ndf_fixing: None,
+                lifecycle: None,
+                compliance_hold: compliance_hold.clone(),
+                approval_status,
+                submitted_at: BlockTime::now(),
+                matched_at: None,
+                paid_at: None,
+            },
+        );
+        self.record_transition(&trade_details.trade_id, None, "Trade submitted".to_string())?;
+        if !is_awaiting_approval {
+            self.send_trade(trade_details, compliance_hold);
+        }
+
+        Ok(())
+    }
+
+    /// Calls signed with the operator key are made by the bank account itself.
+    fn is_operator(&self) -> bool {
+        env::predecessor_account_id() == env::current_account_id()
+            && self.operator_key.as_ref() == Some(&env::signer_account_pk())
+    }
+
+    fn assert_factory_or_admin(&self) -> Result<(), ContractError> {
+        let predecessor_id = env::predecessor_account_id();
+        if predecessor_id != self.factory && predecessor_id != self.admin {
+            return Err(ContractError::NotAdmin);
+        }
+
+        Ok(())
+    }
+
+    /// The caller needs to be an authorised approver other than the submitter of the trade.
+    fn assert_approver(&self, trade_id: &str) -> Result<AccountId, ContractError> {
+        let approver = env::predecessor_account_id();
+        if !self.approvers.contains(&approver) {
+            return Err(ContractError::NotApprover);
+        }
+        let trade = self
+            .trades
+            .get(trade_id)
+            .ok_or(ContractError::InvalidTradeId)?;
+        match &trade.approval_status {
+            Some(ApprovalStatus::AwaitingApproval { submitted_by })
+                if *submitted_by == approver.to_string() =>
+            {
+                Err(ContractError::SubmitterCannotApprove)
+            }
+            Some(ApprovalStatus::AwaitingApproval { .. }) => Ok(approver),
+            _ => Err(ContractError::TradeNotAwaitingApproval),
+        }
+    }
+
+    /// Appends the transition of a trade from `old_state` to its current state to its audit trail.
+    /// The storage of the new entry needs to be covered by the storage balance.
+    fn record_transition(
+        &mut self,
+        trade_id: &str,
+        old_state: Option<TradeState>,
+        message: String,
+    ) -> Result<(), ContractError> {
+        self.assert_storage_covered(AUDIT_ENTRY_STORAGE_BYTES)?;
+
+        let entry = AuditEntry {
+            old_state,
+            new_state: TradeState::from(self.trades.get(trade_id).unwrap()),
+            predecessor_id: env::predecessor_account_id(),
+            block_height: env::block_height(),
+            block_timestamp_ms: env::block_timestamp_ms(),
+            message,
+        };
+        let length = self
+            .trade_history_lengths
+            .get(trade_id)
+            .copied()
+            .unwrap_or_default();
+        self.trade_history
+            .insert((trade_id.to_string(), length), entry);
+        self.trade_history_lengths
+            .insert(trade_id.to_string(), length + 1);
+
+        Ok(())
+    }
+
+    /// Storage usage and `reserve_bytes` of additional storage need to be covered by the storage balance.
+    fn assert_storage_covered(&self, reserve_bytes: u64) -> Result<(), ContractError> {
+        let required =
+            (self.get_storage_usage() + reserve_bytes) as Balance * env::storage_byte_cost();
+        if required > self.storage_balance {
+            return Err(ContractError::StorageDepositExhausted(
+                required - self.storage_balance,
+            ));
+        }
+
+        Ok(())
+    }
 }
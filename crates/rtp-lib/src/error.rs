@@ -1,6 +1,6 @@
 use near_sdk::{
     borsh::{self, BorshSerialize},
-    FunctionError,
+    Balance, FunctionError,
 };
 use thiserror::Error;
 
@@ -8,6 +8,12 @@ use thiserror::Error;
 pub enum ContractError {
     #[error("Only the factory contract can call this function")]
     NotFactory,
+    #[error("Only the admin can call this function")]
+    NotAdmin,
+    #[error("Migration failed: {_0}")]
+    MigrationFailed(String),
+    #[error("Counterparty is not allowed")]
+    CounterpartyNotAllowed,
     #[error("Invalid bank")]
     InvalidBank,
     #[error("Trade ID does not exist")]
@@ -16,4 +22,32 @@ pub enum ContractError {
     TradeIncomplete,
     #[error("Trade match not yet confirmed")]
     TradeMatchUnconfirmed,
+    #[error("NDF has not yet been fixed")]
+    NdfNotYetFixed,
+    #[error("Trade is not a swap")]
+    NotASwap,
+    #[error("Trade is held back for compliance reasons: {_0}")]
+    ComplianceHold(String),
+    #[error("Only authorised approvers can call this function")]
+    NotApprover,
+    #[error("Trade has not been approved")]
+    TradeNotApproved,
+    #[error("Trade is not awaiting approval")]
+    TradeNotAwaitingApproval,
+    #[error("Trade cannot be approved by its submitter")]
+    SubmitterCannotApprove,
+    #[error("Invalid approval threshold")]
+    InvalidApprovalThreshold,
+    #[error("Trade not permitted: {_0}")]
+    TradeNotPermitted(String),
+    #[error("Storage deposit exhausted. Required top-up: {_0}")]
+    StorageDepositExhausted(Balance),
+    #[error("Not enough available storage balance. Requested: {_0}; available: {_1}")]
+    NotEnoughStorageBalance(Balance, Balance),
+    #[error("Bank is paused")]
+    Paused,
+    #[error("Bank has no operator key")]
+    NoOperatorKey,
+    #[error("Operators can only confirm payments received by their bank")]
+    NotCreditor,
 }
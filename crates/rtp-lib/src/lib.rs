@@ -1,14 +1,60 @@
 mod contract;
 mod error;
+mod migration;
 
 pub use contract::*;
 pub use error::*;
 use near_sdk::{
     borsh::{self, BorshSerialize},
-    BorshStorageKey,
+    ext_contract, BorshStorageKey,
 };
+use rtp_contract_common::{LifecycleAction, PartnershipTerms, TradeDetails};
+
+/// Storage, which is covered by the deposit attached when the factory creates the bank.
+const INITIAL_STORAGE_BYTES: u64 = 1024;
+
+/// Storage, which needs to be covered by the storage balance before a new trade is accepted.
+const TRADE_STORAGE_RESERVE_BYTES: u64 = 4096;
+
+/// Storage, which needs to be covered by the storage balance before a transition of a trade is recorded.
+const AUDIT_ENTRY_STORAGE_BYTES: u64 = 512;
 
 #[derive(BorshStorageKey, BorshSerialize)]
 pub enum StorageKey {
     Trades,
+    AllowedCounterparties,
+    ApprovalThresholds,
+    Approvers,
+    Traders,
+    Desks,
+    TradeHistory,
+    TradeHistoryLengths,
+}
+
+#[ext_contract(factory)]
+trait Factory {
+    fn perform_trade(&mut self, bank_id: String, trade_details: TradeDetails);
+
+    fn propose_partnership(
+        &mut self,
+        bank_id: String,
+        counterparty: String,
+        terms: PartnershipTerms,
+    );
+
+    fn accept_partnership(&mut self, bank_id: String, counterparty: String);
+
+    fn confirm_net_payment(&mut self, bank_id: String, cycle_id: u64, instruction_id: u32);
+
+    fn confirm_payment(&mut self, creditor_id: String, debitor_id: String, trade_id: String);
+
+    fn release_trade(&mut self, bank_id: String, partnership_id: String, trade_id: String);
+
+    fn request_lifecycle(
+        &mut self,
+        bank_id: String,
+        partnership_id: String,
+        trade_id: String,
+        action: LifecycleAction,
+    );
 }
@@ -0,0 +1,139 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize},
+    store::UnorderedMap,
+    AccountId,
+};
+use rtp_contract_common::{
+    BlockTime, MatchingStatus, PaymentStatus, Payments, Product, Settlement, Side, Trade,
+    TradeDetails,
+};
+
+/// State of the bank contract, before it tracked its admin, policies and the audit trail of its trades.
+#[derive(BorshDeserialize)]
+pub(crate) struct LegacyContract {
+    pub factory: AccountId,
+    pub bank: String,
+    pub trades: UnorderedMap<String, LegacyTrade>,
+}
+
+#[derive(BorshDeserialize)]
+pub(crate) struct LegacyTrade {
+    bank: String,
+    trade_details: LegacyTradeDetails,
+    matching_status: MatchingStatus,
+    payment_status: PaymentStatus,
+    payments: Payments,
+}
+
+impl From<LegacyTrade> for Trade {
+    fn from(legacy: LegacyTrade) -> Self {
+        Self {
+            bank: legacy.bank,
+            trade_details: legacy.trade_details.into(),
+            matching_status: legacy.matching_status,
+            payment_status: legacy.payment_status,
+            payments: legacy.payments,
+            ndf_fixing: None,
+            lifecycle: None,
+            compliance_hold: None,
+            approval_status: None,
+            submitted_at: BlockTime::default(),
+            matched_at: None,
+            paid_at: None,
+        }
+    }
+}
+
+/// Defines the trade details without NDF terms and swap legs
+/// and their conversion into the current trade details.
+macro_rules! legacy_trade_details {
+    ($($field:ident: $ty:ty,)*) => {
+        #[derive(BorshDeserialize)]
+        struct LegacyTradeDetails {
+            $($field: $ty,)*
+        }
+
+        impl From<LegacyTradeDetails> for TradeDetails {
+            fn from(legacy: LegacyTradeDetails) -> Self {
+                Self {
+                    $($field: legacy.$field,)*
+                    ndf: None,
+                    swap_leg: None,
+                }
+            }
+        }
+    };
+}
+
+legacy_trade_details! {
+    event_timestamp: u64,
+    recv_time: u64,
+    instrument_id: String,
+    asset_class: String,
+    product: Product,
+    side: Side,
+    price: f32,
+    notional_amount: f32,
+    event_type: String,
+    venue: String,
+    trading_platform: String,
+    source_data: String,
+    source_connection: String,
+    trade_id: String,
+    execution_date: String,
+    trader_id: String,
+    account: String,
+    counterparty: String,
+    counterparty_id: String,
+    ccy: String,
+    legal_entity_id: String,
+    legal_entity: String,
+    termination_date: String,
+    buyer: String,
+    seller_id: String,
+    seller: String,
+    effective_date: String,
+    contract_typology: String,
+    client_loco: String,
+    mx_family: String,
+    mx_group: String,
+    mx_type: String,
+    agreement: String,
+    settlement_method: String,
+    limits: bool,
+    authorization: bool,
+    aml_check: bool,
+    sanctions: bool,
+    settlement_pvp: Settlement,
+    delivery_method: String,
+    delivery_timestap: u64,
+    delivery_date: String,
+    time_zone: String,
+    payment_calendar: String,
+    rtp1_ccy: String,
+    rtp1_fee: f32,
+    rtp1_timestamp: u64,
+    rtp2_ccy: String,
+    rtp2_fee: f32,
+    rtp2_timestamp: u64,
+    execution_place: String,
+    ccy1_value_date: String,
+    ccy2_value_date: Option<String>,
+    dealt_ccy: String,
+    ccy1_discount_factor: f32,
+    ccy1_payer_party_id: String,
+    ccy1_payment_amt: f32,
+    ccy1_payment_ccy: String,
+    ccy1_payer_book_id: Option<String>,
+    ccy1_rec_book_id: Option<String>,
+    ccy1_payment_date_u: String,
+    ccy2_discount_factor: Option<f32>,
+    ccy2_payer_party_id: Option<String>,
+    ccy2_payment_amt: Option<f32>,
+    ccy2_payment_ccy: Option<String>,
+    ccy2_payer_book_id: Option<String>,
+    ccy2_rec_book_id: Option<String>,
+    ccy2_payment_date_u: Option<String>,
+    secondary_trade_id: String,
+    source_trade_id: String,
+}
@@ -0,0 +1,80 @@
+//! Minimal NEP-141 token for testing payment-versus-payment settlement with tokenized cash.
+//! Accounts can be registered and funded freely, so it must never be deployed outside of tests.
+
+use near_contract_standards::fungible_token::{
+    core::FungibleTokenCore, resolver::FungibleTokenResolver, FungibleToken,
+};
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    near_bindgen, AccountId, PanicOnDefault, PromiseOrValue,
+};
+
+#[near_bindgen]
+#[derive(BorshSerialize, BorshDeserialize, PanicOnDefault)]
+pub struct Contract {
+    token: FungibleToken,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[init]
+    pub fn new() -> Self {
+        Self {
+            token: FungibleToken::new(b"t".to_vec()),
+        }
+    }
+
+    /// Register an account without requiring a storage deposit.
+    pub fn storage_deposit(&mut self, account_id: AccountId) {
+        if !self.token.accounts.contains_key(&account_id) {
+            self.token.internal_register_account(&account_id);
+        }
+    }
+
+    /// Mint tokens to a registered account.
+    pub fn mint(&mut self, account_id: AccountId, amount: U128) {
+        self.token.internal_deposit(&account_id, amount.0);
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenCore for Contract {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        self.token.ft_transfer(receiver_id, amount, memo)
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.token.ft_transfer_call(receiver_id, amount, memo, msg)
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenResolver for Contract {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        self.token
+            .ft_resolve_transfer(sender_id, receiver_id, amount)
+    }
+}
@@ -5,8 +5,18 @@ mod util;
 #[cfg(not(feature = "testnet"))]
 mod sandbox {
     use crate::util::*;
-    use near_workspaces::types::NearToken;
-    use rtp_contract_common::{MatchingStatus, Product, RtpEvent, Settlement, Side, TradeDetails};
+    use near_sdk::json_types::U128;
+    use near_workspaces::{
+        types::{KeyType, NearToken, SecretKey},
+        Account,
+    };
+    use rtp_contract_common::{
+        AdminOperation, ApprovalStatus, BlockTime, DeskPermissions, LifecycleAction,
+        MatchingStatus, MatchingStatusUpdate, MultisigConfig, NdfFixing, NettingCycleStatus,
+        OperatorKey, PartnershipStatus, PauseScope, PaymentConfirmation, PaymentLeg, PaymentStatus,
+        PaymentStatusUpdate, PaymentWindow, Product, QueueStatus, Role, RtpEvent, Settlement, Side,
+        StatusUpdateOutcome, TradeDetails, TradeLatency, TradeSubmission, TraderPermissions,
+    };
 
     const RTP_WASM: &[u8] = include_bytes!("../../../res/rtp.wasm");
 
@@ -21,7 +31,7 @@ mod sandbox {
 
     #[tokio::test]
     async fn test_create_bank() -> anyhow::Result<()> {
-        let (_, contract) = initialize_contracts().await?;
+        let (worker, contract) = initialize_contracts().await?;
         let bank_a = "Deutsche Bank".to_string();
         let bank_b = "Sparkasse".to_string();
 
@@ -40,24 +50,146 @@ mod sandbox {
             }],
         )?;
 
+        // deposit above the storage cost is refunded
+        let excess_deposit = NearToken::from_near(5).as_yoctonear();
+        call::create_bank(
+            &contract,
+            &bank_b,
+            NearToken::from_yoctonear(storage_cost + excess_deposit),
+        )
+        .await?;
+        let bank_id = view::get_bank_id(&contract, &bank_b).await?;
+        let account_id = format!("{bank_id}.{}", contract.id()).parse()?;
+        let balance = worker.view_account(&account_id).await?.balance;
+        assert!(balance.as_yoctonear() < storage_cost + excess_deposit / 2);
+
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_perform_trade_success() -> anyhow::Result<()> {
+    async fn test_bank_creation_failure() -> anyhow::Result<()> {
         let (_, contract) = initialize_contracts().await?;
         let bank_a = "Deutsche Bank".to_string();
-        let bank_b = "Sparkasse".to_string();
 
-        call::store_contract(&contract, contract.as_account(), RTP_WASM.to_vec()).await?;
+        // without the bank contract binary, the bank account cannot be initialized
+        let storage_cost = view::get_bank_storage_cost(&contract).await?;
+        let (_, events) =
+            call::create_bank(&contract, &bank_a, NearToken::from_yoctonear(storage_cost)).await?;
+        let bank_id = view::get_bank_id(&contract, &bank_a).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::BankCreationFailed {
+                bank: bank_a.clone(),
+                bank_id: bank_id.clone(),
+                reason: "Creating or initializing the bank account failed: Failed".to_string(),
+            }],
+        )?;
+        assert!(view::get_bank_ids(&contract, None, None).await?.is_empty());
 
+        // the bank can be created, once the binary has been stored
+        call::store_contract(&contract, contract.as_account(), RTP_WASM.to_vec()).await?;
         let storage_cost = view::get_bank_storage_cost(&contract).await?;
         call::create_bank(&contract, &bank_a, NearToken::from_yoctonear(storage_cost)).await?;
-        call::create_bank(&contract, &bank_b, NearToken::from_yoctonear(storage_cost)).await?;
-        let bank_a_id = view::get_bank_id(&contract, &bank_a).await?;
-        let bank_b_id = view::get_bank_id(&contract, &bank_b).await?;
-        let partnership_id =
-            view::get_partnership_id(&contract, bank_a.as_str(), bank_b.as_str()).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_partnership() -> anyhow::Result<()> {
+        let (_, contract) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = create_banks(&contract).await?;
+        let terms = default_partnership_terms();
+
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&contract, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        // the factory cannot consent on behalf of a bank
+        assert!(contract
+            .call("propose_partnership")
+            .args_json((&bank_a_id, &bank_b, &terms))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()
+            .is_err());
+
+        let (_, events) = call::propose_partnership(&contract, &bank_a_id, &bank_b, &terms).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::ProposePartnership {
+                partnership_id: partnership_id.clone(),
+                bank_id: bank_a_id.clone(),
+                terms: terms.clone(),
+            }],
+        )?;
+        assert!(call::perform_trade(&contract, &bank_a_id, &trade)
+            .await
+            .is_err());
+        assert!(call::accept_partnership(&contract, &bank_a_id, &bank_b)
+            .await
+            .is_err());
+
+        let (_, events) = call::accept_partnership(&contract, &bank_b_id, &bank_a).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::NewPartnership {
+                partnership_id: partnership_id.clone(),
+                bank_a: bank_a.clone(),
+                bank_b: bank_b.clone(),
+                terms,
+            }],
+        )?;
+        let partnership = view::get_partnership(&contract, &partnership_id)
+            .await?
+            .unwrap();
+        assert_eq!(partnership.status, PartnershipStatus::Active);
+
+        // bank A has not yet allowed trading with bank B
+        assert!(call::perform_trade(&contract, &bank_a_id, &trade)
+            .await
+            .is_err());
+        call::set_counterparty_allowed(&contract, &bank_a_id, &bank_b, true).await?;
+
+        // bank A has not yet deposited for the storage of its trades
+        assert!(call::perform_trade(&contract, &bank_a_id, &trade)
+            .await
+            .is_err());
+        call::storage_deposit(&contract, &bank_a_id, NearToken::from_near(1)).await?;
+        call::perform_trade(&contract, &bank_a_id, &trade).await?;
+        let trade = TradeDetails {
+            trade_id: "trade_id_gbp".to_string(),
+            ccy: "GBP".to_string(),
+            counterparty: bank_b,
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&contract, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_perform_trade_success() -> anyhow::Result<()> {
+        let (_, contract) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&contract).await?;
 
         let mut trade = TradeDetails {
             product: Product::Spot,
@@ -72,6 +204,7 @@ mod sandbox {
                 partnership_id: partnership_id.clone(),
                 bank_id: bank_a_id,
                 trade: trade.clone(),
+                block_time: BlockTime::default(),
             }],
         )?;
 
@@ -84,6 +217,7 @@ mod sandbox {
                 partnership_id,
                 bank_id: bank_b_id,
                 trade,
+                block_time: BlockTime::default(),
             }],
         )?;
 
@@ -93,18 +227,13 @@ mod sandbox {
     #[tokio::test]
     async fn test_settle_trade_success() -> anyhow::Result<()> {
         let (_, contract) = initialize_contracts().await?;
-        let bank_a = "Deutsche Bank".to_string();
-        let bank_b = "Sparkasse".to_string();
-
-        call::store_contract(&contract, contract.as_account(), RTP_WASM.to_vec()).await?;
-
-        let storage_cost = view::get_bank_storage_cost(&contract).await?;
-        call::create_bank(&contract, &bank_a, NearToken::from_yoctonear(storage_cost)).await?;
-        call::create_bank(&contract, &bank_b, NearToken::from_yoctonear(storage_cost)).await?;
-        let bank_a_id = view::get_bank_id(&contract, &bank_a).await?;
-        let bank_b_id = view::get_bank_id(&contract, &bank_b).await?;
-        let partnership_id =
-            view::get_partnership_id(&contract, bank_a.as_str(), bank_b.as_str()).await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&contract).await?;
 
         let mut trade = TradeDetails {
             product: Product::Spot,
@@ -132,6 +261,7 @@ mod sandbox {
                 partnership_id,
                 trade_id: "trade_id".to_string(),
                 matching_status: MatchingStatus::Confirmed("Trade successfull".to_string()),
+                block_time: BlockTime::default(),
             }],
         )?;
 
@@ -139,20 +269,102 @@ mod sandbox {
     }
 
     #[tokio::test]
-    async fn test_confirm_payment() -> anyhow::Result<()> {
+    async fn test_exposure_limit() -> anyhow::Result<()> {
         let (worker, factory) = initialize_contracts().await?;
-        let bank_a = "Deutsche Bank".to_string();
-        let bank_b = "Sparkasse".to_string();
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        call::set_exposure_limit(&factory, &partnership_id, "EUR", Some(1_500.)).await?;
 
-        call::store_contract(&factory, factory.as_account(), RTP_WASM.to_vec()).await?;
+        for trade_id in ["trade_1", "trade_2"] {
+            let mut trade = TradeDetails {
+                trade_id: trade_id.to_string(),
+                side: Side::Buy,
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            };
+            call::perform_trade(&factory, &bank_a_id, &trade).await?;
+            trade.side = Side::Sell;
+            trade.counterparty.clone_from(&bank_a);
+            call::perform_trade(&factory, &bank_b_id, &trade).await?;
+        }
+        let exposure = view::get_exposure(&factory, &partnership_id, "EUR")
+            .await?
+            .unwrap();
+        assert_eq!(exposure.submitted, 2_000.);
+        assert_eq!(exposure.matched, 0.);
 
-        let storage_cost = view::get_bank_storage_cost(&factory).await?;
-        call::create_bank(&factory, &bank_a, NearToken::from_yoctonear(storage_cost)).await?;
-        call::create_bank(&factory, &bank_b, NearToken::from_yoctonear(storage_cost)).await?;
-        let bank_a_id = view::get_bank_id(&factory, &bank_a).await?;
-        let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
-        let partnership_id =
-            view::get_partnership_id(&factory, bank_a.as_str(), bank_b.as_str()).await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_1",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        let (_, events) = call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_2",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::LimitBreach {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_2".to_string(),
+                    ccy: "EUR".to_string(),
+                    limit: 1_500.,
+                    exposure: 1_000.,
+                    amount: 1_000.,
+                },
+                RtpEvent::SetMatchingStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_2".to_string(),
+                    matching_status: MatchingStatus::Rejected(
+                        "Exposure limit breached".to_string(),
+                    ),
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        assert_trade_matching_status(
+            &worker,
+            &account_a_id,
+            "trade_2",
+            &MatchingStatus::Rejected("".to_string()),
+        )
+        .await?;
+        let exposure = view::get_exposure(&factory, &partnership_id, "EUR")
+            .await?
+            .unwrap();
+        assert_eq!(exposure.submitted, 0.);
+        assert_eq!(exposure.matched, 1_000.);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_confirm_payment() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
 
         let mut trade = TradeDetails {
             product: Product::Spot,
@@ -193,4 +405,2512 @@ mod sandbox {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_bilateral_netting() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        for (trade_id, side, notional_amount) in [
+            ("trade_1", Side::Buy, 1_000.),
+            ("trade_2", Side::Sell, 400.),
+        ] {
+            let mut trade = TradeDetails {
+                trade_id: trade_id.to_string(),
+                side: side.clone(),
+                notional_amount,
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            };
+            call::perform_trade(&factory, &bank_a_id, &trade).await?;
+            trade.side = match side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            trade.counterparty.clone_from(&bank_a);
+            call::perform_trade(&factory, &bank_b_id, &trade).await?;
+            call::set_matching_status(
+                &factory,
+                &partnership_id,
+                &bank_a_id,
+                &bank_b_id,
+                trade_id,
+                &MatchingStatus::Confirmed("Trade successfull".to_string()),
+            )
+            .await?;
+        }
+
+        let obligations =
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023").await?;
+        assert_eq!(obligations.len(), 2);
+        assert_eq!(obligations[0].ccy, "EUR");
+        assert_eq!(obligations[0].payer, bank_b_id);
+        assert_eq!(obligations[0].receiver, bank_a_id);
+        assert!((obligations[0].amount - 600.).abs() < 0.01);
+        assert_eq!(obligations[1].ccy, "USD");
+        assert_eq!(obligations[1].payer, bank_a_id);
+        assert_eq!(obligations[1].receiver, bank_b_id);
+        assert!((obligations[1].amount - 690.).abs() < 0.01);
+
+        let (res, _) = call::run_netting(&factory, &partnership_id, "11.12.2023").await?;
+        let cycle_id: u64 = res.json()?;
+        assert!(
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023")
+                .await?
+                .is_empty()
+        );
+
+        // the factory cannot confirm net payments on behalf of the banks
+        assert!(factory
+            .call("confirm_net_payment")
+            .args_json((&bank_a_id, cycle_id, 0))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()
+            .is_err());
+        call::confirm_net_payment(&factory, &bank_a_id, cycle_id, 0).await?;
+        let (_, events) = call::confirm_net_payment(&factory, &bank_b_id, cycle_id, 1).await?;
+        let payment_status =
+            PaymentStatus::Confirmed(format!("Settled in netting cycle {cycle_id}"));
+        assert_event_emits(
+            events[1..].to_vec(),
+            vec![
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_1".to_string(),
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_2".to_string(),
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::SettleNettingCycle {
+                    cycle_id,
+                    status: NettingCycleStatus::Settled,
+                },
+            ],
+        )?;
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let account_b_id = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        for trade_id in ["trade_1", "trade_2"] {
+            assert_trade_payment_status(&worker, &account_a_id, trade_id, &payment_status).await?;
+            assert_trade_payment_status(&worker, &account_b_id, trade_id, &payment_status).await?;
+            let trade = view::get_trade(&worker, &account_b_id, trade_id).await?;
+            assert!(trade.payments.credit);
+            assert!(trade.payments.debit);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_netting_cycle_failure() -> anyhow::Result<()> {
+        let (_, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        let mut trade = TradeDetails {
+            trade_id: "trade_1".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        trade.side = Side::Sell;
+        trade.counterparty.clone_from(&bank_a);
+        call::perform_trade(&factory, &bank_b_id, &trade).await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_1",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        // the bank contracts refuse to mark a trade as paid, whose match is no longer confirmed
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_1",
+            &MatchingStatus::Rejected("Trade cancelled".to_string()),
+        )
+        .await?;
+
+        let (res, _) = call::run_netting(&factory, &partnership_id, "11.12.2023").await?;
+        let cycle_id: u64 = res.json()?;
+        call::confirm_net_payment(&factory, &bank_a_id, cycle_id, 0).await?;
+        let (_, events) = call::confirm_net_payment(&factory, &bank_a_id, cycle_id, 1).await?;
+        assert_event_emits(
+            events[1..].to_vec(),
+            vec![RtpEvent::SettleNettingCycle {
+                cycle_id,
+                status: NettingCycleStatus::Error,
+            }],
+        )?;
+
+        // the net payments have been made, so the trades are not netted again
+        assert!(call::run_netting(&factory, &partnership_id, "11.12.2023")
+            .await
+            .is_err());
+
+        // once the match is confirmed again, the trades of the cycle are marked as paid
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_1",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        let (_, events) = call::retry_netting_cycle(&factory, cycle_id).await?;
+        let payment_status =
+            PaymentStatus::Confirmed(format!("Settled in netting cycle {cycle_id}"));
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_1".to_string(),
+                    payment_status,
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::SettleNettingCycle {
+                    cycle_id,
+                    status: NettingCycleStatus::Settled,
+                },
+            ],
+        )?;
+        assert!(call::retry_netting_cycle(&factory, cycle_id).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multilateral_netting() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let banks = ["Deutsche Bank", "Sparkasse", "JPMorgan"];
+
+        call::store_contract(&factory, factory.as_account(), RTP_WASM.to_vec()).await?;
+
+        let storage_cost = view::get_bank_storage_cost(&factory).await?;
+        let mut bank_ids = vec![];
+        for bank in banks {
+            call::create_bank(&factory, bank, NearToken::from_yoctonear(storage_cost)).await?;
+            bank_ids.push(view::get_bank_id(&factory, bank).await?);
+        }
+
+        // A buys from B, B buys from C and C buys from A
+        for (buyer, seller, notional_amount) in [(0, 1, 1_000.), (1, 2, 1_000.), (2, 0, 500.)] {
+            setup_partnership(
+                &factory,
+                &bank_ids[buyer],
+                banks[buyer],
+                &bank_ids[seller],
+                banks[seller],
+            )
+            .await?;
+            let partnership_id =
+                view::get_partnership_id(&factory, banks[buyer], banks[seller]).await?;
+            let trade_id = format!("{}:{}", banks[buyer], banks[seller]);
+            let mut trade = TradeDetails {
+                trade_id: trade_id.clone(),
+                side: Side::Buy,
+                notional_amount,
+                counterparty: banks[seller].to_string(),
+                ..Default::default()
+            };
+            call::perform_trade(&factory, &bank_ids[buyer], &trade).await?;
+            trade.side = Side::Sell;
+            trade.counterparty = banks[buyer].to_string();
+            call::perform_trade(&factory, &bank_ids[seller], &trade).await?;
+            call::set_matching_status(
+                &factory,
+                &partnership_id,
+                &bank_ids[buyer],
+                &bank_ids[seller],
+                &trade_id,
+                &MatchingStatus::Confirmed("Trade successfull".to_string()),
+            )
+            .await?;
+        }
+
+        let positions = view::get_net_positions(&factory, None).await?;
+        let position = |bank_id: &str, ccy: &str| {
+            positions
+                .iter()
+                .find(|position| position.bank_id == bank_id && position.ccy == ccy)
+                .unwrap()
+                .amount
+        };
+        assert!((position(&bank_ids[0], "EUR") - 500.).abs() < 0.01);
+        assert!(position(&bank_ids[1], "EUR").abs() < 0.01);
+        assert!((position(&bank_ids[2], "EUR") + 500.).abs() < 0.01);
+        assert!((position(&bank_ids[0], "USD") + 575.).abs() < 0.01);
+        assert!(position(&bank_ids[1], "USD").abs() < 0.01);
+        assert!((position(&bank_ids[2], "USD") - 575.).abs() < 0.01);
+
+        let (res, _) = call::run_multilateral_netting(&factory, None).await?;
+        let cycle_id: u64 = res.json()?;
+        let cycle = view::get_netting_cycle(&factory, cycle_id).await?.unwrap();
+        assert_eq!(cycle.trades.len(), 3);
+        assert_eq!(cycle.instructions.len(), 2);
+        assert_eq!(cycle.instructions[0].payer, bank_ids[2]);
+        assert_eq!(cycle.instructions[0].receiver, bank_ids[0]);
+        assert_eq!(cycle.instructions[1].payer, bank_ids[0]);
+        assert_eq!(cycle.instructions[1].receiver, bank_ids[2]);
+
+        // bank B neither pays nor receives any net payment
+        assert!(
+            call::confirm_net_payment(&factory, &bank_ids[1], cycle_id, 0)
+                .await
+                .is_err()
+        );
+        call::confirm_net_payment(&factory, &bank_ids[2], cycle_id, 0).await?;
+        call::confirm_net_payment(&factory, &bank_ids[0], cycle_id, 1).await?;
+        let cycle = view::get_netting_cycle(&factory, cycle_id).await?.unwrap();
+        assert_eq!(cycle.status, NettingCycleStatus::Settled);
+
+        let payment_status =
+            PaymentStatus::Confirmed(format!("Settled in netting cycle {cycle_id}"));
+        for (bank_id, trade_id) in [
+            (&bank_ids[1], "Deutsche Bank:Sparkasse"),
+            (&bank_ids[1], "Sparkasse:JPMorgan"),
+            (&bank_ids[0], "JPMorgan:Deutsche Bank"),
+        ] {
+            let account_id = format!("{bank_id}.{}", factory.id()).parse()?;
+            assert_trade_payment_status(&worker, &account_id, trade_id, &payment_status).await?;
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pvp_settlement() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        let treasury_a = worker.dev_create_account().await?;
+        let treasury_b = worker.dev_create_account().await?;
+        let treasuries = [treasury_a.id(), treasury_b.id()];
+        let eur = initialize_token(&worker, &factory, "EUR", &treasuries, 1_000_000).await?;
+        let usd = initialize_token(&worker, &factory, "USD", &treasuries, 1_000_000).await?;
+        call::set_settlement_account(&factory, &bank_a_id, Some(treasury_a.id())).await?;
+        call::set_settlement_account(&factory, &bank_b_id, Some(treasury_b.id())).await?;
+
+        // bank A buys 1,000.00 EUR for 1,150.00 USD
+        let mut trade = TradeDetails {
+            trade_id: "trade_id".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        trade.side = Side::Sell;
+        trade.counterparty.clone_from(&bank_a);
+        call::perform_trade(&factory, &bank_b_id, &trade).await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+
+        let msg = |bank_id: &str| {
+            serde_json::json!({
+                "partnership_id": partnership_id,
+                "trade_id": "trade_id",
+                "bank_id": bank_id,
+            })
+            .to_string()
+        };
+
+        // only the settlement account of the paying bank can fund its leg
+        call::ft_transfer_call(
+            &usd,
+            &treasury_b,
+            factory.id(),
+            U128(115_000),
+            msg(&bank_a_id),
+        )
+        .await?;
+        assert_eq!(
+            view::ft_balance_of(&usd, treasury_b.id()).await?,
+            U128(1_000_000)
+        );
+        assert!(view::get_pvp_escrow(&factory, &partnership_id, "trade_id")
+            .await?
+            .is_none());
+
+        // excess tokens are returned to the sender
+        let (_, events) = call::ft_transfer_call(
+            &usd,
+            &treasury_a,
+            factory.id(),
+            U128(120_000),
+            msg(&bank_a_id),
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::DepositPvp {
+                partnership_id: partnership_id.clone(),
+                trade_id: "trade_id".to_string(),
+                bank_id: bank_a_id.clone(),
+                ccy: "USD".to_string(),
+                amount: U128(115_000),
+            }],
+        )?;
+        assert_eq!(
+            view::ft_balance_of(&usd, treasury_a.id()).await?,
+            U128(885_000)
+        );
+        assert!(view::get_pvp_escrow(&factory, &partnership_id, "trade_id")
+            .await?
+            .is_some());
+
+        // a paused bank can't settle, so its deposit is returned
+        let scope = PauseScope::Bank {
+            bank_id: bank_b_id.clone(),
+        };
+        call::pause(&factory, factory.as_account(), &scope).await?;
+        call::ft_transfer_call(
+            &eur,
+            &treasury_b,
+            factory.id(),
+            U128(100_000),
+            msg(&bank_b_id),
+        )
+        .await?;
+        assert_eq!(
+            view::ft_balance_of(&eur, treasury_b.id()).await?,
+            U128(1_000_000)
+        );
+        let escrow = view::get_pvp_escrow(&factory, &partnership_id, "trade_id")
+            .await?
+            .unwrap();
+        assert!(!escrow.is_funded());
+        call::unpause(&factory, factory.as_account(), &scope).await?;
+
+        let (_, events) = call::ft_transfer_call(
+            &eur,
+            &treasury_b,
+            factory.id(),
+            U128(100_000),
+            msg(&bank_b_id),
+        )
+        .await?;
+        let payment_status = PaymentStatus::Confirmed("Settled via PvP".to_string());
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::DepositPvp {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                    bank_id: bank_b_id.clone(),
+                    ccy: "EUR".to_string(),
+                    amount: U128(100_000),
+                },
+                RtpEvent::SettlePvp {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                },
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+        assert!(view::get_pvp_escrow(&factory, &partnership_id, "trade_id")
+            .await?
+            .is_none());
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let account_b_id = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        assert_trade_payment_status(&worker, &account_a_id, "trade_id", &payment_status).await?;
+        assert_trade_payment_status(&worker, &account_b_id, "trade_id", &payment_status).await?;
+
+        assert_eq!(
+            view::get_token_balance(&factory, treasury_a.id(), eur.id()).await?,
+            U128(100_000)
+        );
+        assert_eq!(
+            view::get_token_balance(&factory, treasury_b.id(), usd.id()).await?,
+            U128(115_000)
+        );
+        call::ft_withdraw(&factory, &treasury_a, eur.id(), None).await?;
+        assert_eq!(
+            view::get_token_balance(&factory, treasury_a.id(), eur.id()).await?,
+            U128(0)
+        );
+        assert_eq!(
+            view::ft_balance_of(&eur, treasury_a.id()).await?,
+            U128(1_100_000)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_settlement_queue() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        let treasury = worker.dev_create_account().await?;
+        let eur = initialize_token(&worker, &factory, "EUR", &[treasury.id()], 1_000_000).await?;
+        let usd = initialize_token(&worker, &factory, "USD", &[treasury.id()], 1_000_000).await?;
+
+        for (trade_id, side) in [
+            ("trade_1", Side::Buy),
+            ("trade_2", Side::Sell),
+            ("trade_3", Side::Buy),
+        ] {
+            let mut trade = TradeDetails {
+                trade_id: trade_id.to_string(),
+                side: side.clone(),
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            };
+            call::perform_trade(&factory, &bank_a_id, &trade).await?;
+            trade.side = match side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            trade.counterparty.clone_from(&bank_a);
+            call::perform_trade(&factory, &bank_b_id, &trade).await?;
+            call::set_matching_status(
+                &factory,
+                &partnership_id,
+                &bank_a_id,
+                &bank_b_id,
+                trade_id,
+                &MatchingStatus::Confirmed("Trade successfull".to_string()),
+            )
+            .await?;
+        }
+
+        let queue_event = |trade_id: &str, bank_id: &str, ccy: &str, status: QueueStatus| {
+            RtpEvent::SettlementQueue {
+                partnership_id: partnership_id.clone(),
+                trade_id: trade_id.to_string(),
+                bank_id: bank_id.to_string(),
+                ccy: ccy.to_string(),
+                amount: U128(if ccy == "EUR" { 100_000 } else { 115_000 }),
+                priority: 0,
+                status,
+            }
+        };
+
+        // without any liquidity, neither trade can settle on its own
+        let (_, events) = call::queue_settlement(&factory, &partnership_id, "trade_1", 0).await?;
+        assert_event_emits(
+            events,
+            vec![
+                queue_event(
+                    "trade_1",
+                    &bank_b_id,
+                    "EUR",
+                    QueueStatus::Queued(
+                        "Insufficient liquidity: 0 of 100000 available".to_string(),
+                    ),
+                ),
+                queue_event(
+                    "trade_1",
+                    &bank_a_id,
+                    "USD",
+                    QueueStatus::Queued(
+                        "Insufficient liquidity: 0 of 115000 available".to_string(),
+                    ),
+                ),
+            ],
+        )?;
+        assert_eq!(
+            view::get_settlement_queue(&factory, &bank_b_id, "EUR")
+                .await?
+                .len(),
+            1
+        );
+
+        // but both trades offset each other
+        let (_, events) = call::queue_settlement(&factory, &partnership_id, "trade_2", 0).await?;
+        let reason = "Settled via gridlock resolution".to_string();
+        let payment_status = PaymentStatus::Confirmed(reason.clone());
+        assert_event_emits(
+            events,
+            vec![
+                queue_event(
+                    "trade_2",
+                    &bank_a_id,
+                    "EUR",
+                    QueueStatus::Queued(
+                        "Insufficient liquidity: 0 of 100000 available".to_string(),
+                    ),
+                ),
+                queue_event(
+                    "trade_2",
+                    &bank_b_id,
+                    "USD",
+                    QueueStatus::Queued(
+                        "Insufficient liquidity: 0 of 115000 available".to_string(),
+                    ),
+                ),
+                queue_event(
+                    "trade_1",
+                    &bank_b_id,
+                    "EUR",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                queue_event(
+                    "trade_1",
+                    &bank_a_id,
+                    "USD",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                queue_event(
+                    "trade_2",
+                    &bank_a_id,
+                    "EUR",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                queue_event(
+                    "trade_2",
+                    &bank_b_id,
+                    "USD",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_1".to_string(),
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_2".to_string(),
+                    payment_status: payment_status.clone(),
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        for trade_id in ["trade_1", "trade_2"] {
+            assert_trade_payment_status(&worker, &account_a_id, trade_id, &payment_status).await?;
+        }
+
+        // trade waits for liquidity of bank B
+        call::queue_settlement(&factory, &partnership_id, "trade_3", 0).await?;
+        let (_, events) = call::ft_transfer_call(
+            &eur,
+            &treasury,
+            factory.id(),
+            U128(100_000),
+            serde_json::json!({ "bank_id": bank_b_id }).to_string(),
+        )
+        .await?;
+        assert!(events.is_empty());
+        assert_eq!(
+            view::get_liquidity(&factory, &bank_b_id, "EUR").await?,
+            U128(100_000)
+        );
+        assert_eq!(
+            view::get_settlement_queue(&factory, &bank_b_id, "EUR")
+                .await?
+                .len(),
+            1
+        );
+
+        let (_, events) = call::ft_transfer_call(
+            &usd,
+            &treasury,
+            factory.id(),
+            U128(115_000),
+            serde_json::json!({ "bank_id": bank_a_id }).to_string(),
+        )
+        .await?;
+        let reason = "Settled from available liquidity".to_string();
+        assert_event_emits(
+            events,
+            vec![
+                queue_event(
+                    "trade_3",
+                    &bank_b_id,
+                    "EUR",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                queue_event(
+                    "trade_3",
+                    &bank_a_id,
+                    "USD",
+                    QueueStatus::Settled(reason.clone()),
+                ),
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_3".to_string(),
+                    payment_status: PaymentStatus::Confirmed(reason),
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+        assert_eq!(
+            view::get_liquidity(&factory, &bank_a_id, "EUR").await?,
+            U128(100_000)
+        );
+        assert!(view::get_settlement_queue(&factory, &bank_b_id, "EUR")
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ndf_fixing() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        // NDF without NDF terms is rejected
+        let mut trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ndf: None,
+            ..TradeDetails::default_ndf()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        // bank A buys USD/BRL at 5.00, settled in USD
+        trade.ndf = TradeDetails::default_ndf().ndf;
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        trade.side = Side::Sell;
+        trade.counterparty.clone_from(&bank_a);
+        call::perform_trade(&factory, &bank_b_id, &trade).await?;
+
+        // only matched trades can be fixed
+        let rate_provider = worker.dev_create_account().await?;
+        assert!(
+            call::submit_fixing(&factory, &rate_provider, &partnership_id, "trade_id", 5.5)
+                .await
+                .is_err()
+        );
+        call::grant_role(&factory, Role::RateProvider, rate_provider.id()).await?;
+        assert!(
+            call::submit_fixing(&factory, &rate_provider, &partnership_id, "trade_id", 5.5)
+                .await
+                .is_err()
+        );
+
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        assert!(
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023")
+                .await?
+                .is_empty()
+        );
+        // payments of an NDF can only be confirmed after its fixing
+        assert!(
+            call::confirm_payment(&factory, &bank_a_id, &bank_b_id, "trade_id")
+                .await
+                .is_err()
+        );
+
+        let (_, events) =
+            call::submit_fixing(&factory, &rate_provider, &partnership_id, "trade_id", 5.5).await?;
+        let fixing = NdfFixing {
+            rate: 5.5,
+            settlement: PaymentLeg {
+                payer: bank_b_id.clone(),
+                receiver: bank_a_id.clone(),
+                ccy: "USD".to_string(),
+                amount: 1_000. * (5.5 - 5.) / 5.5,
+            },
+        };
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SubmitFixing {
+                partnership_id: partnership_id.clone(),
+                trade_id: "trade_id".to_string(),
+                fixing: fixing.clone(),
+            }],
+        )?;
+        assert!(
+            call::submit_fixing(&factory, &rate_provider, &partnership_id, "trade_id", 5.6)
+                .await
+                .is_err()
+        );
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let trade = view::get_trade(&worker, &account_a_id, "trade_id").await?;
+        assert_eq!(trade.ndf_fixing, Some(fixing.clone()));
+
+        let obligations =
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023").await?;
+        assert_eq!(obligations.len(), 1);
+        assert_eq!(obligations[0].payer, bank_b_id);
+        assert_eq!(obligations[0].receiver, bank_a_id);
+        assert!((obligations[0].amount - fixing.settlement.amount).abs() < 0.01);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_swap_legs() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        // swap leg without linked leg is rejected
+        let near_leg = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..TradeDetails::default_swap()
+        };
+        assert!(call::perform_trade(
+            &factory,
+            &bank_a_id,
+            &TradeDetails {
+                secondary_trade_id: Default::default(),
+                ..near_leg.clone()
+            }
+        )
+        .await
+        .is_err());
+
+        // bank A buys EUR/USD on the near leg and sells it back on the far leg
+        let far_leg = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..TradeDetails::default_swap_far()
+        };
+        for leg in [near_leg, far_leg] {
+            call::perform_trade(&factory, &bank_a_id, &leg).await?;
+            let counterparty_leg = TradeDetails {
+                side: match leg.side {
+                    Side::Buy => Side::Sell,
+                    Side::Sell => Side::Buy,
+                },
+                counterparty: bank_a.clone(),
+                ..leg.clone()
+            };
+            call::perform_trade(&factory, &bank_b_id, &counterparty_leg).await?;
+            call::set_matching_status(
+                &factory,
+                &partnership_id,
+                &bank_a_id,
+                &bank_b_id,
+                &leg.trade_id,
+                &MatchingStatus::Confirmed("Trade successfull".to_string()),
+            )
+            .await?;
+        }
+
+        // each leg settles on its own value date
+        let payment_status = PaymentStatus::Confirmed("Payment successfull".to_string());
+        let (_, events) = call::set_payment_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &payment_status,
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SetPaymentStatus {
+                partnership_id: partnership_id.clone(),
+                trade_id: "trade_id".to_string(),
+                payment_status: payment_status.clone(),
+                block_time: BlockTime::default(),
+            }],
+        )?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        assert!(!view::is_swap_complete(&worker, &account_a_id, "trade_id").await?);
+
+        let (_, events) = call::set_payment_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "far_trade_id",
+            &payment_status,
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::CompleteSwap {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_a_id.clone(),
+                    near_trade_id: "trade_id".to_string(),
+                    far_trade_id: "far_trade_id".to_string(),
+                },
+                RtpEvent::CompleteSwap {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_b_id.clone(),
+                    near_trade_id: "trade_id".to_string(),
+                    far_trade_id: "far_trade_id".to_string(),
+                },
+                RtpEvent::SetPaymentStatus {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "far_trade_id".to_string(),
+                    payment_status,
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+        assert!(view::is_swap_complete(&worker, &account_a_id, "trade_id").await?);
+        assert!(view::is_swap_complete(&worker, &account_a_id, "far_trade_id").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_forward_lifecycle() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        // bank A buys a EUR/USD forward
+        let mut trade = TradeDetails {
+            product: Product::Fwd,
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        trade.side = Side::Sell;
+        trade.counterparty.clone_from(&bank_a);
+        call::perform_trade(&factory, &bank_b_id, &trade).await?;
+
+        let roll = LifecycleAction::Roll {
+            new_trade_id: "rolled_trade_id".to_string(),
+            value_date: "11.03.2024".to_string(),
+            price: 1.17,
+        };
+        // unmatched forwards cannot be rolled
+        assert!(
+            call::request_lifecycle(&factory, &bank_a_id, &partnership_id, "trade_id", &roll)
+                .await
+                .is_err()
+        );
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+
+        // the new value date needs to be a later business day
+        for value_date in ["09.03.2024", "08.12.2023", "31.02.2024"] {
+            let invalid_roll = LifecycleAction::Roll {
+                new_trade_id: "rolled_trade_id".to_string(),
+                value_date: value_date.to_string(),
+                price: 1.17,
+            };
+            assert!(call::request_lifecycle(
+                &factory,
+                &bank_a_id,
+                &partnership_id,
+                "trade_id",
+                &invalid_roll
+            )
+            .await
+            .is_err());
+        }
+
+        // roll is applied, as soon as both banks requested it
+        let (_, events) =
+            call::request_lifecycle(&factory, &bank_a_id, &partnership_id, "trade_id", &roll)
+                .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::RequestLifecycle {
+                partnership_id: partnership_id.clone(),
+                trade_id: "trade_id".to_string(),
+                bank_id: bank_a_id.clone(),
+                action: roll.clone(),
+            }],
+        )?;
+        let (_, events) =
+            call::request_lifecycle(&factory, &bank_b_id, &partnership_id, "trade_id", &roll)
+                .await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::RequestLifecycle {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                    bank_id: bank_b_id.clone(),
+                    action: roll.clone(),
+                },
+                RtpEvent::RollTrade {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                    new_trade_id: "rolled_trade_id".to_string(),
+                    value_date: "11.03.2024".to_string(),
+                    price: 1.17,
+                },
+            ],
+        )?;
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let original_trade = view::get_trade(&worker, &account_a_id, "trade_id").await?;
+        assert_eq!(original_trade.lifecycle, Some(roll));
+        let rolled_trade = view::get_trade(&worker, &account_a_id, "rolled_trade_id").await?;
+        assert_eq!(rolled_trade.trade_details.source_trade_id, "trade_id");
+        assert_eq!(rolled_trade.trade_details.ccy1_value_date, "11.03.2024");
+        assert!(
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023")
+                .await?
+                .is_empty()
+        );
+        assert_eq!(
+            view::get_net_obligations(&factory, &partnership_id, "11.03.2024")
+                .await?
+                .len(),
+            2
+        );
+
+        // differing requests do not terminate the rolled forward
+        let close_out = PaymentLeg {
+            payer: bank_b_id.clone(),
+            receiver: bank_a_id.clone(),
+            ccy: "USD".to_string(),
+            amount: 20.,
+        };
+        assert!(call::request_lifecycle(
+            &factory,
+            &bank_a_id,
+            &partnership_id,
+            "rolled_trade_id",
+            &LifecycleAction::Terminate {
+                close_out: close_out.clone(),
+                value_date: "16.12.2023".to_string(),
+            },
+        )
+        .await
+        .is_err());
+        let terminate = LifecycleAction::Terminate {
+            close_out: close_out.clone(),
+            value_date: "13.12.2023".to_string(),
+        };
+        call::request_lifecycle(
+            &factory,
+            &bank_a_id,
+            &partnership_id,
+            "rolled_trade_id",
+            &terminate,
+        )
+        .await?;
+        let (_, events) = call::request_lifecycle(
+            &factory,
+            &bank_b_id,
+            &partnership_id,
+            "rolled_trade_id",
+            &LifecycleAction::Terminate {
+                close_out: PaymentLeg {
+                    amount: 10.,
+                    ..close_out.clone()
+                },
+                value_date: "13.12.2023".to_string(),
+            },
+        )
+        .await?;
+        assert_eq!(events.len(), 1);
+
+        let (_, events) = call::request_lifecycle(
+            &factory,
+            &bank_a_id,
+            &partnership_id,
+            "rolled_trade_id",
+            &terminate,
+        )
+        .await?;
+        assert_eq!(events.len(), 1);
+        let (_, events) = call::request_lifecycle(
+            &factory,
+            &bank_b_id,
+            &partnership_id,
+            "rolled_trade_id",
+            &terminate,
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::RequestLifecycle {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "rolled_trade_id".to_string(),
+                    bank_id: bank_b_id.clone(),
+                    action: terminate.clone(),
+                },
+                RtpEvent::TerminateTrade {
+                    partnership_id: partnership_id.clone(),
+                    trade_id: "rolled_trade_id".to_string(),
+                    close_out: close_out.clone(),
+                    value_date: "13.12.2023".to_string(),
+                },
+            ],
+        )?;
+
+        let obligations =
+            view::get_net_obligations(&factory, &partnership_id, "13.12.2023").await?;
+        assert_eq!(obligations.len(), 1);
+        assert_eq!(obligations[0].payer, bank_b_id);
+        assert_eq!(obligations[0].receiver, bank_a_id);
+        assert_eq!(obligations[0].amount, 20.);
+        // the terminated forward is closed and replaced by its close-out
+        assert!(
+            view::get_net_obligations(&factory, &partnership_id, "11.03.2024")
+                .await?
+                .is_empty()
+        );
+        let terminated_trade = view::get_trade(&worker, &account_a_id, "rolled_trade_id").await?;
+        assert_eq!(terminated_trade.lifecycle, Some(terminate));
+        let close_out_trade =
+            view::get_trade(&worker, &account_a_id, "rolled_trade_id-close-out").await?;
+        assert_eq!(
+            close_out_trade.trade_details.source_trade_id,
+            "rolled_trade_id"
+        );
+        assert_eq!(close_out_trade.trade_details.ccy1_value_date, "13.12.2023");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_holiday_calendar() -> anyhow::Result<()> {
+        let (_, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        assert!(call::set_holidays(&factory, "NY", &["25.13.2023"])
+            .await
+            .is_err());
+        call::set_holidays(&factory, "NY", &["25.12.2023"]).await?;
+        call::set_holidays(&factory, "LN", &["25.12.2023", "26.12.2023"]).await?;
+
+        assert_eq!(
+            view::get_value_date(&factory, "NY", "22.12.2023", &Settlement::T(2)).await?,
+            "27.12.2023"
+        );
+        assert_eq!(
+            view::get_value_date(&factory, "NYLN", "22.12.2023", &Settlement::T(2)).await?,
+            "28.12.2023"
+        );
+        assert_eq!(
+            view::get_value_date(&factory, "NYLN", "23.12.2023", &Settlement::RealTime).await?,
+            "27.12.2023"
+        );
+
+        // value dates on weekends, holidays or in an invalid format are rejected
+        for value_date in ["09.12.2023", "26.12.2023", "2023-12-11"] {
+            let trade = TradeDetails {
+                counterparty: bank_b.clone(),
+                ccy1_value_date: value_date.to_string(),
+                ..Default::default()
+            };
+            assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+                .await
+                .is_err());
+        }
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ccy1_value_date: "27.12.2023".to_string(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_payment_cut_off() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        let mut window = PaymentWindow {
+            time_zone: "Europe/Frankfurt".to_string(),
+            utc_offset_minutes: 60,
+            opens_at: 16 * 60,
+            cut_off: 7 * 60,
+        };
+        assert!(call::set_payment_window(&factory, "EUR", Some(&window))
+            .await
+            .is_err());
+        window.opens_at = 0;
+        window.cut_off = 24 * 60;
+        call::set_payment_window(&factory, "EUR", Some(&window)).await?;
+
+        // the cut-off on the value date of the trade has already passed
+        let mut trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        trade.side = Side::Sell;
+        trade.counterparty.clone_from(&bank_a);
+        call::perform_trade(&factory, &bank_b_id, &trade).await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        assert!(
+            call::confirm_payment(&factory, &bank_a_id, &bank_b_id, "trade_id")
+                .await
+                .is_err()
+        );
+        assert!(call::set_payment_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &PaymentStatus::Confirmed("Payment successfull".to_string()),
+        )
+        .await
+        .is_err());
+        let (res, _) = call::set_payment_statuses(
+            &factory,
+            &[PaymentStatusUpdate {
+                partnership_id: partnership_id.clone(),
+                bank_a_id: bank_a_id.clone(),
+                bank_b_id: bank_b_id.clone(),
+                trade_id: "trade_id".to_string(),
+                payment_status: PaymentStatus::Confirmed("Payment successfull".to_string()),
+            }],
+        )
+        .await?;
+        let outcomes: Vec<StatusUpdateOutcome> = res.json()?;
+        assert!(outcomes[0].error.is_some());
+
+        // only the trades in the given page are checked
+        let (_, events) = call::check_cut_offs(&factory, Some(1), None).await?;
+        assert_event_emits(events, vec![])?;
+        let (_, events) = call::check_cut_offs(&factory, None, None).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::MissCutOff {
+                partnership_id: partnership_id.clone(),
+                trade_id: "trade_id".to_string(),
+                ccy: "EUR".to_string(),
+                value_date: "11.12.2023".to_string(),
+            }],
+        )?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let trade = view::get_trade(&worker, &account_a_id, "trade_id").await?;
+        assert!(matches!(
+            trade.payment_status,
+            PaymentStatus::MissedCutOff(_)
+        ));
+        assert!(
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2023")
+                .await?
+                .is_empty()
+        );
+
+        // both banks can roll the missed trade to a new value date
+        let roll = LifecycleAction::Roll {
+            new_trade_id: "rolled_trade_id".to_string(),
+            value_date: "11.12.2098".to_string(),
+            price: 1.1,
+        };
+        for bank_id in [&bank_a_id, &bank_b_id] {
+            call::request_lifecycle(&factory, bank_id, &partnership_id, "trade_id", &roll).await?;
+        }
+        let rolled_trade = view::get_trade(&worker, &account_a_id, "rolled_trade_id").await?;
+        assert_eq!(rolled_trade.trade_details.ccy1_value_date, "11.12.2098");
+        assert_eq!(
+            view::get_net_obligations(&factory, &partnership_id, "11.12.2098")
+                .await?
+                .len(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compliance_hold() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+
+        // trades with failed AML check cannot be matched
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            aml_check: false,
+            ..Default::default()
+        };
+        let (_, events) = call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::SendTrade {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_a_id.clone(),
+                    trade: trade.clone(),
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::ComplianceHold {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_a_id.clone(),
+                    trade_id: "trade_id".to_string(),
+                    reason: "AML check failed".to_string(),
+                },
+            ],
+        )?;
+        call::perform_trade(
+            &factory,
+            &bank_b_id,
+            &TradeDetails {
+                side: Side::Sell,
+                counterparty: bank_a.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        assert_trade_matching_status(&worker, &account_a_id, "trade_id", &MatchingStatus::Error)
+            .await?;
+
+        // only compliance officers can maintain the screening list
+        let compliance_officer = worker.dev_create_account().await?;
+        assert!(
+            call::add_screened_counterparty(&factory, &compliance_officer, &bank_b_id)
+                .await
+                .is_err()
+        );
+        call::grant_role(&factory, Role::Compliance, compliance_officer.id()).await?;
+        call::add_screened_counterparty(&factory, &compliance_officer, &bank_b_id).await?;
+
+        let trade = TradeDetails {
+            trade_id: "screened_trade_id".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        let (_, events) = call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::SendTrade {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_a_id.clone(),
+                    trade: trade.clone(),
+                    block_time: BlockTime::default(),
+                },
+                RtpEvent::ComplianceHold {
+                    partnership_id: partnership_id.clone(),
+                    bank_id: bank_a_id.clone(),
+                    trade_id: "screened_trade_id".to_string(),
+                    reason: "Counterparty is on the screening list".to_string(),
+                },
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_counterparty_allow_list() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        assert_eq!(
+            view::get_allowed_counterparties(&worker, &account_a_id).await?,
+            vec![bank_b.clone()]
+        );
+
+        // trades with unknown counterparties are rejected
+        let trade = TradeDetails {
+            counterparty: "Unknown Bank".to_string(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        // trades with disallowed counterparties are rejected
+        call::set_counterparty_allowed(&factory, &bank_a_id, "Another Bank", true).await?;
+        call::set_counterparty_allowed(&factory, &bank_a_id, &bank_b, false).await?;
+        assert_eq!(
+            view::get_allowed_counterparties(&worker, &account_a_id).await?,
+            vec!["Another Bank".to_string()]
+        );
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        // trades rejected by the bank contract are not tracked as exposure
+        let partnership_id =
+            view::get_partnership_id(&factory, bank_a.as_str(), bank_b.as_str()).await?;
+        assert!(view::get_exposure(&factory, &partnership_id, "EUR")
+            .await?
+            .is_none());
+
+        // an empty allow-list allows all counterparties
+        call::set_counterparty_allowed(&factory, &bank_a_id, "Another Bank", false).await?;
+        assert!(view::get_allowed_counterparties(&worker, &account_a_id)
+            .await?
+            .is_empty());
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        let exposure = view::get_exposure(&factory, &partnership_id, "EUR")
+            .await?
+            .unwrap();
+        assert_eq!(exposure.submitted, 1_000.);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_four_eyes_approval() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+
+        let approver = worker.dev_create_account().await?;
+        call::set_approval_threshold(&factory, &bank_a_id, "EUR", Some(500.)).await?;
+        call::set_approver(&factory, &bank_a_id, approver.id(), true).await?;
+        call::set_approver(&factory, &bank_a_id, factory.id(), true).await?;
+
+        // trades above the threshold are not sent for matching
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        let (_, events) = call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        assert_event_emits(events, vec![])?;
+        assert_eq!(
+            view::get_trade(&worker, &account_a_id, "trade_id")
+                .await?
+                .approval_status,
+            Some(ApprovalStatus::AwaitingApproval {
+                submitted_by: factory.id().to_string(),
+            })
+        );
+        call::perform_trade(
+            &factory,
+            &bank_b_id,
+            &TradeDetails {
+                side: Side::Sell,
+                counterparty: bank_a.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        assert_trade_matching_status(&worker, &account_a_id, "trade_id", &MatchingStatus::Error)
+            .await?;
+
+        // the submitter cannot approve its own trade
+        assert!(
+            call::approve_trade(factory.as_account(), &account_a_id, "trade_id")
+                .await
+                .is_err()
+        );
+
+        let (_, events) = call::approve_trade(&approver, &account_a_id, "trade_id").await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SendTrade {
+                partnership_id: partnership_id.clone(),
+                bank_id: bank_a_id.clone(),
+                trade: trade.clone(),
+                block_time: BlockTime::default(),
+            }],
+        )?;
+        assert!(call::reject_trade(&approver, &account_a_id, "trade_id")
+            .await
+            .is_err());
+
+        // rejected trades are never sent for matching
+        let trade = TradeDetails {
+            trade_id: "rejected_trade_id".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        let (_, events) = call::reject_trade(&approver, &account_a_id, "rejected_trade_id").await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::RejectTrade {
+                partnership_id: partnership_id.clone(),
+                bank_id: bank_a_id.clone(),
+                trade_id: "rejected_trade_id".to_string(),
+                approver: approver.id().to_string(),
+            }],
+        )?;
+        let exposure = view::get_exposure(&factory, &partnership_id, "EUR")
+            .await?
+            .unwrap();
+        assert_eq!(exposure.submitted, 0.);
+
+        // trades below the threshold are sent immediately
+        let trade = TradeDetails {
+            trade_id: "small_trade_id".to_string(),
+            counterparty: bank_b.clone(),
+            notional_amount: 100.,
+            ..Default::default()
+        };
+        let (_, events) = call::perform_trade(&factory, &bank_a_id, &trade).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SendTrade {
+                partnership_id: partnership_id.clone(),
+                bank_id: bank_a_id.clone(),
+                trade,
+                block_time: BlockTime::default(),
+            }],
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trader_permissions() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        let (_, events) = call::set_permissions_enforced(&factory, &bank_a_id, true).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::EnforcePermissions {
+                bank_id: bank_a_id.clone(),
+                enforced: true,
+            }],
+        )?;
+
+        // trades of unknown traders are rejected
+        let trade = TradeDetails {
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        let trader_permissions = TraderPermissions {
+            desks: vec![trade.account.clone()],
+            legal_entity_ids: vec![trade.legal_entity_id.clone()],
+            products: vec![Product::Spot, Product::Fwd],
+        };
+        let (_, events) = call::set_trader_permissions(
+            &factory,
+            &bank_a_id,
+            &trade.trader_id,
+            Some(&trader_permissions),
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SetTraderPermissions {
+                bank_id: bank_a_id.clone(),
+                trader_id: trade.trader_id.clone(),
+                permissions: Some(trader_permissions),
+            }],
+        )?;
+        let desk_permissions = DeskPermissions {
+            legal_entity_ids: vec![trade.legal_entity_id.clone()],
+            products: vec![Product::Spot],
+        };
+        let (_, events) = call::set_desk_permissions(
+            &factory,
+            &bank_a_id,
+            &trade.account,
+            Some(&desk_permissions),
+        )
+        .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SetDeskPermissions {
+                bank_id: bank_a_id.clone(),
+                desk: trade.account.clone(),
+                permissions: Some(desk_permissions),
+            }],
+        )?;
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+
+        // products need to be permitted for both the trader and the desk
+        let trade = TradeDetails {
+            trade_id: "fwd_trade_id".to_string(),
+            product: Product::Fwd,
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        // trades into other desks are rejected
+        let trade = TradeDetails {
+            trade_id: "other_desk_trade_id".to_string(),
+            account: "67890".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trade_history() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+
+        assert!(view::get_trade_history(&worker, &account_a_id, "trade_id")
+            .await
+            .is_err());
+
+        call::perform_trade(
+            &factory,
+            &bank_a_id,
+            &TradeDetails {
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        call::perform_trade(
+            &factory,
+            &bank_b_id,
+            &TradeDetails {
+                side: Side::Sell,
+                counterparty: bank_a.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+
+        let history = view::get_trade_history(&worker, &account_a_id, "trade_id").await?;
+        assert_eq!(history.len(), 2);
+        assert!(history[0].old_state.is_none());
+        assert!(matches!(
+            history[0].new_state.matching_status,
+            MatchingStatus::Pending
+        ));
+        assert_eq!(history[0].message, "Trade submitted");
+        assert!(matches!(
+            history[1].old_state.as_ref().unwrap().matching_status,
+            MatchingStatus::Pending
+        ));
+        assert!(matches!(
+            history[1].new_state.matching_status,
+            MatchingStatus::Confirmed(_)
+        ));
+        assert_eq!(history[1].predecessor_id.as_str(), factory.id().as_str());
+        assert!(history[0].block_height < history[1].block_height);
+        assert!(history[0].block_timestamp_ms < history[1].block_timestamp_ms);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trade_block_times() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+
+        call::perform_trade(
+            &factory,
+            &bank_a_id,
+            &TradeDetails {
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let trade = view::get_trade(&worker, &account_a_id, "trade_id").await?;
+        assert!(trade.submitted_at.block_height > 0);
+        assert!(trade.matched_at.is_none());
+        assert_eq!(
+            view::get_trade_latency(&worker, &account_a_id, "trade_id").await?,
+            TradeLatency {
+                matching_ms: None,
+                payment_ms: None,
+            }
+        );
+
+        call::perform_trade(
+            &factory,
+            &bank_b_id,
+            &TradeDetails {
+                side: Side::Sell,
+                counterparty: bank_a.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_id",
+            &MatchingStatus::Confirmed("Trade successfull".to_string()),
+        )
+        .await?;
+        let trade = view::get_trade(&worker, &account_a_id, "trade_id").await?;
+        let matched_at = trade.matched_at.unwrap();
+        assert!(matched_at.block_height > trade.submitted_at.block_height);
+        let latency = view::get_trade_latency(&worker, &account_a_id, "trade_id").await?;
+        assert_eq!(
+            latency.matching_ms,
+            Some(matched_at.timestamp_ms - trade.submitted_at.timestamp_ms)
+        );
+        assert!(latency.payment_ms.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_storage_deposit() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+
+        let storage_balance = view::storage_balance_of(&worker, &account_a_id).await?;
+        assert_eq!(
+            storage_balance.total.0,
+            NearToken::from_near(1).as_yoctonear()
+        );
+        assert_eq!(view::get_storage_top_up(&worker, &account_a_id).await?.0, 0);
+
+        // storage of trades is charged against the storage balance
+        call::perform_trade(
+            &factory,
+            &bank_a_id,
+            &TradeDetails {
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let storage_balance = view::storage_balance_of(&worker, &account_a_id).await?;
+        assert!(storage_balance.available.0 < storage_balance.total.0);
+
+        // the used storage balance cannot be withdrawn
+        assert!(
+            call::storage_withdraw(&factory, &bank_a_id, Some(U128(storage_balance.total.0)))
+                .await
+                .is_err()
+        );
+        call::storage_withdraw(&factory, &bank_a_id, None).await?;
+        let storage_balance = view::storage_balance_of(&worker, &account_a_id).await?;
+        assert_eq!(storage_balance.available.0, 0);
+
+        // new trades are rejected, until the storage balance is topped up
+        let trade = TradeDetails {
+            trade_id: "new_trade_id".to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade)
+            .await
+            .is_err());
+        let top_up = view::get_storage_top_up(&worker, &account_a_id).await?;
+        assert!(top_up.0 > 0);
+        call::storage_deposit(&factory, &bank_a_id, NearToken::from_yoctonear(top_up.0)).await?;
+        call::perform_trade(&factory, &bank_a_id, &trade).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_bank() -> anyhow::Result<()> {
+        let (_, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            ..
+        } = setup_banks(&factory).await?;
+        let bank_c = "Commerzbank".to_string();
+        let storage_cost = view::get_bank_storage_cost(&factory).await?;
+        call::create_bank(&factory, &bank_c, NearToken::from_yoctonear(storage_cost)).await?;
+        let bank_c_id = view::get_bank_id(&factory, &bank_c).await?;
+
+        call::perform_trade(
+            &factory,
+            &bank_a_id,
+            &TradeDetails {
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // only suspended banks can be removed
+        assert!(call::remove_bank(&factory, &bank_a_id).await.is_err());
+
+        let (_, events) = call::suspend_bank(&factory, &bank_a_id, true).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::SuspendBank {
+                bank_id: bank_a_id.clone(),
+                suspended: true,
+            }],
+        )?;
+
+        // suspended banks cannot trade
+        assert!(call::perform_trade(
+            &factory,
+            &bank_b_id,
+            &TradeDetails {
+                trade_id: "new_trade_id".to_string(),
+                counterparty: bank_a.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .is_err());
+
+        // banks with open trades cannot be removed
+        assert!(call::remove_bank(&factory, &bank_a_id).await.is_err());
+
+        // banks without open trades are removed and their trades are archived
+        call::suspend_bank(&factory, &bank_c_id, true).await?;
+        let (_, events) = call::remove_bank(&factory, &bank_c_id).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::RemoveBank {
+                bank_id: bank_c_id.clone(),
+                archived_trades: 0,
+            }],
+        )?;
+        let bank_ids = view::get_bank_ids(&factory, None, None).await?;
+        assert!(!bank_ids.contains(&bank_c_id));
+        assert!(bank_ids.contains(&bank_a_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emergency_pause() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let partnership_id = view::get_partnership_id(&factory, &bank_a, &bank_b).await?;
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let trade = |trade_id: &str| TradeDetails {
+            trade_id: trade_id.to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+
+        // only pausers can pause
+        let pauser = worker.dev_create_account().await?;
+        assert!(call::pause(&factory, &pauser, &PauseScope::Global)
+            .await
+            .is_err());
+        call::grant_role(&factory, Role::Pauser, pauser.id()).await?;
+
+        let (_, events) = call::pause(&factory, &pauser, &PauseScope::Global).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::Pause {
+                scope: PauseScope::Global,
+                account_id: pauser.id().to_string(),
+            }],
+        )?;
+        assert_eq!(
+            view::get_paused_scopes(&factory).await?,
+            vec![PauseScope::Global]
+        );
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade("trade_1"))
+            .await
+            .is_err());
+        let (_, events) = call::unpause(&factory, &pauser, &PauseScope::Global).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::Unpause {
+                scope: PauseScope::Global,
+                account_id: pauser.id().to_string(),
+            }],
+        )?;
+        call::perform_trade(&factory, &bank_a_id, &trade("trade_1")).await?;
+
+        // pausing a partnership blocks status changes of its trades
+        let partnership_scope = PauseScope::Partnership {
+            partnership_id: partnership_id.clone(),
+        };
+        call::pause(&factory, &pauser, &partnership_scope).await?;
+        assert!(call::set_matching_status(
+            &factory,
+            &partnership_id,
+            &bank_a_id,
+            &bank_b_id,
+            "trade_1",
+            &MatchingStatus::Confirmed("".to_string()),
+        )
+        .await
+        .is_err());
+        call::unpause(&factory, &pauser, &partnership_scope).await?;
+
+        // pausing a bank also pauses its bank contract
+        let bank_scope = PauseScope::Bank {
+            bank_id: bank_a_id.clone(),
+        };
+        call::pause(&factory, &pauser, &bank_scope).await?;
+        assert!(view::is_bank_paused(&worker, &account_a_id).await?);
+        assert!(call::perform_trade(&factory, &bank_a_id, &trade("trade_2"))
+            .await
+            .is_err());
+        call::unpause(&factory, &pauser, &bank_scope).await?;
+        assert!(!view::is_bank_paused(&worker, &account_a_id).await?);
+        call::perform_trade(&factory, &bank_a_id, &trade("trade_2")).await?;
+        assert!(view::get_paused_scopes(&factory).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ownership_transfer() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let new_owner = worker.dev_create_account().await?;
+        let other = worker.dev_create_account().await?;
+
+        assert_eq!(
+            view::get_owner(&factory).await?.as_str(),
+            factory.id().as_str()
+        );
+
+        // only the owner can propose a new owner
+        assert!(call::propose_owner(&factory, &other, new_owner.id())
+            .await
+            .is_err());
+        let (_, events) =
+            call::propose_owner(&factory, factory.as_account(), new_owner.id()).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::ProposeOwner {
+                owner: factory.id().to_string(),
+                new_owner: new_owner.id().to_string(),
+            }],
+        )?;
+
+        // only the proposed owner can accept
+        assert!(call::accept_ownership(&factory, &other).await.is_err());
+        let (_, events) = call::accept_ownership(&factory, &new_owner).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::TransferOwnership {
+                old_owner: factory.id().to_string(),
+                new_owner: new_owner.id().to_string(),
+            }],
+        )?;
+        assert_eq!(
+            view::get_owner(&factory).await?.as_str(),
+            new_owner.id().as_str()
+        );
+
+        // critical functions are restricted to the new owner
+        assert!(
+            call::store_contract(&factory, factory.as_account(), RTP_WASM.to_vec())
+                .await
+                .is_err()
+        );
+        call::store_contract(&factory, &new_owner, RTP_WASM.to_vec()).await?;
+
+        // banks are created by the new owner, which receives any refund
+        let bank = "Deutsche Bank";
+        let storage_cost = NearToken::from_yoctonear(view::get_bank_storage_cost(&factory).await?);
+        assert!(call::create_bank(&factory, bank, storage_cost)
+            .await
+            .is_err());
+        let (_, events) = call::create_bank_from(&factory, &new_owner, bank, storage_cost).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::NewBank {
+                bank: bank.to_string(),
+                bank_id: view::get_bank_id(&factory, bank).await?,
+            }],
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multisig() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let admin_a = worker.dev_create_account().await?;
+        let admin_b = worker.dev_create_account().await?;
+        let admin_c = worker.dev_create_account().await?;
+        let pauser = worker.dev_create_account().await?;
+        let config = |proposal_ttl_ms: u64| -> anyhow::Result<MultisigConfig> {
+            Ok(MultisigConfig {
+                admins: vec![
+                    admin_a.id().as_str().parse()?,
+                    admin_b.id().as_str().parse()?,
+                    admin_c.id().as_str().parse()?,
+                ],
+                threshold: 2,
+                proposal_ttl_ms,
+            })
+        };
+
+        call::store_contract(&factory, factory.as_account(), RTP_WASM.to_vec()).await?;
+        call::set_multisig(&factory, factory.as_account(), Some(&config(3_600_000)?)).await?;
+
+        // critical functions cannot be called by the owner directly anymore
+        assert!(call::grant_role(&factory, Role::Pauser, pauser.id())
+            .await
+            .is_err());
+        assert!(
+            call::propose_owner(&factory, factory.as_account(), admin_a.id())
+                .await
+                .is_err()
+        );
+
+        // all other functions remain with the owner
+        let storage_cost = view::get_bank_storage_cost(&factory).await?;
+        call::create_bank(
+            &factory,
+            "Deutsche Bank",
+            NearToken::from_yoctonear(storage_cost),
+        )
+        .await?;
+
+        // only admins can propose
+        let operation = AdminOperation::GrantRole {
+            role: Role::Pauser,
+            account_id: pauser.id().as_str().parse()?,
+        };
+        assert!(
+            call::propose_operation(&factory, factory.as_account(), &operation)
+                .await
+                .is_err()
+        );
+        let (res, events) = call::propose_operation(&factory, &admin_a, &operation).await?;
+        let proposal_id: u64 = res.json()?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::ProposeOperation {
+                proposal_id,
+                proposer: admin_a.id().to_string(),
+                operation: "grant_role".to_string(),
+                expires_at_ms: view::get_admin_proposal(&factory, proposal_id)
+                    .await?
+                    .unwrap()
+                    .expires_at_ms,
+            }],
+        )?;
+        assert!(!view::has_role(&factory, Role::Pauser, pauser.id()).await?);
+
+        // the proposer cannot approve twice
+        assert!(call::approve_operation(&factory, &admin_a, proposal_id)
+            .await
+            .is_err());
+
+        // the operation is executed with the second approval
+        let (_, events) = call::approve_operation(&factory, &admin_b, proposal_id).await?;
+        assert_event_emits(
+            events,
+            vec![
+                RtpEvent::ApproveOperation {
+                    proposal_id,
+                    admin: admin_b.id().to_string(),
+                    approvals: 2,
+                },
+                RtpEvent::ExecuteOperation {
+                    proposal_id,
+                    operation: "grant_role".to_string(),
+                },
+            ],
+        )?;
+        assert!(view::has_role(&factory, Role::Pauser, pauser.id()).await?);
+        assert!(call::approve_operation(&factory, &admin_c, proposal_id)
+            .await
+            .is_err());
+
+        // clearing the storage keeps the roles and the admin proposals
+        assert!(call::clear_storage(&factory, factory.as_account())
+            .await
+            .is_err());
+        let (res, _) =
+            call::propose_operation(&factory, &admin_a, &AdminOperation::ClearStorage).await?;
+        let clear_proposal_id: u64 = res.json()?;
+        call::approve_operation(&factory, &admin_b, clear_proposal_id).await?;
+        assert!(view::has_role(&factory, Role::Pauser, pauser.id()).await?);
+        assert!(view::get_admin_proposal(&factory, proposal_id)
+            .await?
+            .is_some());
+
+        // proposals expire
+        let (res, _) = call::propose_operation(
+            &factory,
+            &admin_a,
+            &AdminOperation::SetMultisig {
+                config: Some(config(1)?),
+            },
+        )
+        .await?;
+        let proposal_id: u64 = res.json()?;
+        call::approve_operation(&factory, &admin_c, proposal_id).await?;
+        let (res, _) = call::propose_operation(
+            &factory,
+            &admin_a,
+            &AdminOperation::RevokeRole {
+                role: Role::Pauser,
+                account_id: pauser.id().as_str().parse()?,
+            },
+        )
+        .await?;
+        let proposal_id: u64 = res.json()?;
+        assert!(call::approve_operation(&factory, &admin_b, proposal_id)
+            .await
+            .is_err());
+        assert!(view::has_role(&factory, Role::Pauser, pauser.id()).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operator_key() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let bank_a = "Deutsche Bank".to_string();
+        let bank_b = "Sparkasse".to_string();
+
+        call::store_contract(&factory, factory.as_account(), RTP_WASM.to_vec()).await?;
+
+        let secret_key = SecretKey::from_random(KeyType::ED25519);
+        let operator_key = |secret_key: &SecretKey| -> anyhow::Result<OperatorKey> {
+            Ok(OperatorKey {
+                public_key: secret_key.public_key().to_string().parse()?,
+                allowance: U128(NearToken::from_millinear(250).as_yoctonear()),
+            })
+        };
+        let storage_cost = view::get_bank_storage_cost(&factory).await?;
+        call::create_bank_with_operator_key(
+            &factory,
+            &bank_a,
+            NearToken::from_yoctonear(storage_cost),
+            &operator_key(&secret_key)?,
+        )
+        .await?;
+        call::create_bank(&factory, &bank_b, NearToken::from_yoctonear(storage_cost)).await?;
+        let bank_a_id = view::get_bank_id(&factory, &bank_a).await?;
+        let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
+        setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b).await?;
+        let account_a_id: near_workspaces::AccountId =
+            format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let trade = |trade_id: &str| TradeDetails {
+            trade_id: trade_id.to_string(),
+            counterparty: bank_b.clone(),
+            ..Default::default()
+        };
+
+        // the operator submits trades directly to its bank contract, which are validated by the factory
+        let operator = Account::from_secret_key(account_a_id.clone(), secret_key.clone(), &worker);
+        call::operator_perform_trade(&operator, &trade("trade_1")).await?;
+        view::get_trade(&worker, &account_a_id, "trade_1").await?;
+
+        // the operator can only confirm payments received by its bank
+        assert!(
+            call::operator_confirm_payment(&operator, "trade_1", &PaymentConfirmation::Debit)
+                .await
+                .is_err()
+        );
+
+        // the rotated key replaces the old key
+        let new_secret_key = SecretKey::from_random(KeyType::ED25519);
+        let (_, events) =
+            call::rotate_operator_key(&factory, &bank_a_id, &operator_key(&new_secret_key)?)
+                .await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::RotateOperatorKey {
+                bank_id: bank_a_id.clone(),
+                public_key: new_secret_key.public_key().to_string(),
+            }],
+        )?;
+        assert!(call::operator_perform_trade(&operator, &trade("trade_2"))
+            .await
+            .is_err());
+        let operator =
+            Account::from_secret_key(account_a_id.clone(), new_secret_key.clone(), &worker);
+        call::operator_perform_trade(&operator, &trade("trade_2")).await?;
+
+        // revoked keys cannot be used anymore
+        let (_, events) = call::revoke_operator_key(&factory, &bank_a_id).await?;
+        assert_event_emits(
+            events,
+            vec![RtpEvent::RevokeOperatorKey {
+                bank_id: bank_a_id.clone(),
+                public_key: new_secret_key.public_key().to_string(),
+            }],
+        )?;
+        assert!(call::operator_perform_trade(&operator, &trade("trade_3"))
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_perform_trades() -> anyhow::Result<()> {
+        let (_, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+        let trade = |trade_id: &str, counterparty: &str| TradeDetails {
+            trade_id: trade_id.to_string(),
+            counterparty: counterparty.to_string(),
+            ..Default::default()
+        };
+
+        // every trade is stored or rejected on its own
+        let (res, events) = call::perform_trades(
+            &factory,
+            &bank_a_id,
+            &[
+                trade("trade_1", &bank_b),
+                trade("trade_2", "Unknown Bank"),
+                trade("trade_3", &bank_b),
+            ],
+        )
+        .await?;
+        let submissions: Vec<TradeSubmission> = res.json()?;
+        assert_eq!(
+            submissions
+                .iter()
+                .map(|submission| (submission.trade_id.as_str(), submission.error.is_none()))
+                .collect::<Vec<_>>(),
+            vec![("trade_1", true), ("trade_2", false), ("trade_3", true)]
+        );
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| matches!(
+                    event,
+                    rtp_common::ContractEvent::Rtp(rtp_common::RtpEvent {
+                        event_kind: rtp_common::RtpEventKind::SendTrade(_),
+                        ..
+                    })
+                ))
+                .count(),
+            2
+        );
+
+        // trades rejected by the bank contract are not tracked as exposure
+        let bank_c = "Commerzbank".to_string();
+        call::create_bank(&factory, &bank_c, NearToken::from_yoctonear(storage_cost)).await?;
+        let bank_c_id = view::get_bank_id(&factory, &bank_c).await?;
+        call::propose_partnership(&factory, &bank_a_id, &bank_c, &default_partnership_terms())
+            .await?;
+        call::accept_partnership(&factory, &bank_c_id, &bank_a).await?;
+        let (res, _) = call::perform_trades(
+            &factory,
+            &bank_a_id,
+            &[trade("trade_4", &bank_b), trade("trade_5", &bank_c)],
+        )
+        .await?;
+        let submissions: Vec<TradeSubmission> = res.json()?;
+        assert_eq!(
+            submissions
+                .iter()
+                .map(|submission| (submission.trade_id.as_str(), submission.error.is_none()))
+                .collect::<Vec<_>>(),
+            vec![("trade_4", true), ("trade_5", false)]
+        );
+        let partnership_ab_id =
+            view::get_partnership_id(&factory, bank_a.as_str(), bank_b.as_str()).await?;
+        let partnership_ac_id =
+            view::get_partnership_id(&factory, bank_a.as_str(), bank_c.as_str()).await?;
+        let exposure = view::get_exposure(&factory, &partnership_ab_id, "EUR")
+            .await?
+            .unwrap();
+        assert_eq!(exposure.submitted, 3_000.);
+        assert!(view::get_exposure(&factory, &partnership_ac_id, "EUR")
+            .await?
+            .is_none());
+
+        // batches are limited to the maximum batch size
+        let max_batch_size = view::get_max_trade_batch_size(&factory).await?;
+        let trades: Vec<_> = (0..=max_batch_size)
+            .map(|index| trade(&format!("batch_trade_{index}"), &bank_b))
+            .collect();
+        assert!(call::perform_trades(&factory, &bank_a_id, &trades)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_batch_status_updates() -> anyhow::Result<()> {
+        let (worker, factory) = initialize_contracts().await?;
+        let TestBanks {
+            bank_a,
+            bank_b,
+            bank_a_id,
+            bank_b_id,
+            partnership_id,
+        } = setup_banks(&factory).await?;
+
+        for trade_id in ["trade_1", "trade_2"] {
+            let mut trade = TradeDetails {
+                trade_id: trade_id.to_string(),
+                side: Side::Buy,
+                counterparty: bank_b.clone(),
+                ..Default::default()
+            };
+            call::perform_trade(&factory, &bank_a_id, &trade).await?;
+            trade.side = Side::Sell;
+            trade.counterparty.clone_from(&bank_a);
+            call::perform_trade(&factory, &bank_b_id, &trade).await?;
+        }
+        let matching_update = |bank_b_id: &str, trade_id: &str| MatchingStatusUpdate {
+            partnership_id: partnership_id.clone(),
+            bank_a_id: bank_a_id.clone(),
+            bank_b_id: bank_b_id.to_string(),
+            trade_id: trade_id.to_string(),
+            matching_status: MatchingStatus::Confirmed("Trade successfull".to_string()),
+        };
+
+        // unknown banks and trades only fail their own update
+        let (res, events) = call::set_matching_statuses(
+            &factory,
+            &[
+                matching_update(&bank_b_id, "trade_1"),
+                matching_update("unknown", "trade_2"),
+                matching_update(&bank_b_id, "trade_2"),
+                matching_update(&bank_b_id, "trade_3"),
+            ],
+        )
+        .await?;
+        let outcomes: Vec<StatusUpdateOutcome> = res.json()?;
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(|outcome| (outcome.trade_id.as_str(), outcome.error.is_none()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("trade_1", true),
+                ("trade_2", false),
+                ("trade_2", true),
+                ("trade_3", false)
+            ]
+        );
+        let matching_event =
+            |trade_id: &str, matching_status: MatchingStatus| RtpEvent::SetMatchingStatus {
+                partnership_id: partnership_id.clone(),
+                trade_id: trade_id.to_string(),
+                matching_status,
+                block_time: BlockTime::default(),
+            };
+        assert_event_emits(
+            events,
+            vec![
+                matching_event(
+                    "trade_1",
+                    MatchingStatus::Confirmed("Trade successfull".to_string()),
+                ),
+                matching_event(
+                    "trade_2",
+                    MatchingStatus::Confirmed("Trade successfull".to_string()),
+                ),
+                matching_event("trade_3", MatchingStatus::Error),
+                RtpEvent::SetMatchingStatuses {
+                    outcomes,
+                    block_time: BlockTime::default(),
+                },
+            ],
+        )?;
+
+        let account_a_id = format!("{bank_a_id}.{}", factory.id()).parse()?;
+        let account_b_id = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        for account_id in [&account_a_id, &account_b_id] {
+            for trade_id in ["trade_1", "trade_2"] {
+                assert_trade_matching_status(
+                    &worker,
+                    account_id,
+                    trade_id,
+                    &MatchingStatus::Confirmed("".to_string()),
+                )
+                .await?;
+            }
+        }
+
+        let (res, _) = call::set_payment_statuses(
+            &factory,
+            &[PaymentStatusUpdate {
+                partnership_id: partnership_id.clone(),
+                bank_a_id: bank_a_id.clone(),
+                bank_b_id: bank_b_id.clone(),
+                trade_id: "trade_1".to_string(),
+                payment_status: PaymentStatus::Confirmed("Payment successfull".to_string()),
+            }],
+        )
+        .await?;
+        let outcomes: Vec<StatusUpdateOutcome> = res.json()?;
+        assert!(outcomes.iter().all(|outcome| outcome.error.is_none()));
+        for account_id in [&account_a_id, &account_b_id] {
+            assert_trade_payment_status(
+                &worker,
+                account_id,
+                "trade_1",
+                &PaymentStatus::Confirmed("".to_string()),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
 }
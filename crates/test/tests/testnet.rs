@@ -42,7 +42,7 @@ mod testnet {
     #[test_case(TradeDetails {
         product: Product::Ndf,
         agreement: "Bilateral".to_string(),
-        ..Default::default()
+        ..TradeDetails::default_ndf()
     }; "ndf")]
     #[test_case(TradeDetails {
         product: Product::Fwd,
@@ -88,6 +88,11 @@ mod testnet {
         let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
         let account_a_id: AccountId = format!("{bank_a_id}.{}", factory.id()).parse()?;
         let account_b_id: AccountId = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_b),
+        )
+        .await?;
 
         pause_execution(Duration::from_secs(5));
 
@@ -124,6 +129,14 @@ mod testnet {
 
         run_sub_test(
             async {
+                fix_ndf(
+                    &factory,
+                    &bank_a,
+                    &bank_b,
+                    "trade_id",
+                    &trade_details.product,
+                )
+                .await?;
                 call::confirm_payment(&factory, &bank_a_id, &bank_b_id, "trade_id").await?;
                 call::confirm_payment(&factory, &bank_b_id, &bank_a_id, "trade_id").await?;
 
@@ -160,7 +173,7 @@ mod testnet {
     #[test_case(TradeDetails {
         product: Product::Ndf,
         agreement: "Bilateral".to_string(),
-        ..Default::default()
+        ..TradeDetails::default_ndf()
     }; "ndf")]
     #[test_case(TradeDetails {
         product: Product::Fwd,
@@ -222,6 +235,36 @@ mod testnet {
         let account_b_id: AccountId = format!("{bank_b_id}.{}", factory.id()).parse()?;
         let account_c_id: AccountId = format!("{bank_c_id}.{}", factory.id()).parse()?;
         let account_d_id: AccountId = format!("{bank_d_id}.{}", factory.id()).parse()?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_b),
+        )
+        .await?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_c_id, &bank_c),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_c),
+        )
+        .await?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_d_id, &bank_d),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_d),
+        )
+        .await?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_b_id, &bank_b, &bank_c_id, &bank_c),
+            &format!("establish partnership {} <> {}", &bank_b, &bank_c),
+        )
+        .await?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_b_id, &bank_b, &bank_d_id, &bank_d),
+            &format!("establish partnership {} <> {}", &bank_b, &bank_d),
+        )
+        .await?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_c_id, &bank_c, &bank_d_id, &bank_d),
+            &format!("establish partnership {} <> {}", &bank_c, &bank_d),
+        )
+        .await?;
 
         pause_execution(Duration::from_secs(5));
 
@@ -359,6 +402,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_a,
+                &bank_b,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_a_id, &bank_b_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_a),
@@ -387,6 +438,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_a,
+                &bank_c,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_a_id, &bank_c_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_a),
@@ -415,6 +474,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_a,
+                &bank_d,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_a_id, &bank_d_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_a),
@@ -443,6 +510,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_b,
+                &bank_c,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_b_id, &bank_c_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_b),
@@ -471,6 +546,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_b,
+                &bank_d,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_b_id, &bank_d_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_b),
@@ -499,6 +582,14 @@ mod testnet {
             )
             .await?;
 
+            fix_ndf(
+                &factory,
+                &bank_c,
+                &bank_d,
+                &trade_id,
+                &trade_details.product,
+            )
+            .await?;
             run_sub_test(
                 call::confirm_payment(&factory, &bank_c_id, &bank_d_id, &trade_id),
                 &format!("confirm payment for bank {}", &bank_c),
@@ -627,7 +718,7 @@ mod testnet {
     #[test_case(TradeDetails {
         product: Product::Ndf,
         agreement: "Bilateral".to_string(),
-        ..Default::default()
+        ..TradeDetails::default_ndf()
     }; "ndf")]
     #[test_case(TradeDetails {
         product: Product::Fwd,
@@ -673,6 +764,11 @@ mod testnet {
         let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
         let account_a_id: AccountId = format!("{bank_a_id}.{}", factory.id()).parse()?;
         let account_b_id: AccountId = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_b),
+        )
+        .await?;
 
         pause_execution(Duration::from_secs(5));
 
@@ -711,6 +807,14 @@ mod testnet {
 
         run_sub_test(
             async {
+                fix_ndf(
+                    &factory,
+                    &bank_a,
+                    &bank_b,
+                    "trade_id",
+                    &trade_details.product,
+                )
+                .await?;
                 call::confirm_payment(&factory, &bank_a_id, &bank_b_id, "trade_id").await?;
                 call::confirm_payment(&factory, &bank_b_id, &bank_a_id, "trade_id").await?;
 
@@ -747,7 +851,7 @@ mod testnet {
     #[test_case(TradeDetails {
         product: Product::Ndf,
         agreement: "Bilateral".to_string(),
-        ..Default::default()
+        ..TradeDetails::default_ndf()
     }; "ndf")]
     #[test_case(TradeDetails {
         product: Product::Fwd,
@@ -793,6 +897,11 @@ mod testnet {
         let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
         let account_a_id: AccountId = format!("{bank_a_id}.{}", factory.id()).parse()?;
         let account_b_id: AccountId = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_b),
+        )
+        .await?;
 
         pause_execution(Duration::from_secs(5));
 
@@ -829,6 +938,14 @@ mod testnet {
 
         run_sub_test(
             async {
+                fix_ndf(
+                    &factory,
+                    &bank_a,
+                    &bank_b,
+                    "trade_id",
+                    &trade_details.product,
+                )
+                .await?;
                 call::confirm_payment(&factory, &bank_a_id, &bank_b_id, "trade_id").await?;
                 // 2nd party doesn't confirm payment
                 // call::confirm_payment(&factory, &bank_b_id, &bank_a_id, "trade_id").await?;
@@ -866,7 +983,7 @@ mod testnet {
     #[test_case(TradeDetails {
         product: Product::Ndf,
         agreement: "Bilateral".to_string(),
-        ..Default::default()
+        ..TradeDetails::default_ndf()
     }; "ndf")]
     #[test_case(TradeDetails {
         product: Product::Fwd,
@@ -912,6 +1029,11 @@ mod testnet {
         let bank_b_id = view::get_bank_id(&factory, &bank_b).await?;
         let account_a_id: AccountId = format!("{bank_a_id}.{}", factory.id()).parse()?;
         let account_b_id: AccountId = format!("{bank_b_id}.{}", factory.id()).parse()?;
+        run_sub_test(
+            setup_partnership(&factory, &bank_a_id, &bank_a, &bank_b_id, &bank_b),
+            &format!("establish partnership {} <> {}", &bank_a, &bank_b),
+        )
+        .await?;
 
         pause_execution(Duration::from_secs(5));
 
@@ -1,11 +1,16 @@
 use super::log_tx_result;
+use near_sdk::json_types::U128;
 use near_workspaces::{
     result::{ExecutionResult, Value},
     types::NearToken,
-    Account, Contract,
+    Account, AccountId, Contract,
 };
 use rtp_common::ContractEvent;
-use rtp_contract_common::{MatchingStatus, TradeDetails};
+use rtp_contract_common::{
+    AdminOperation, CurrencyToken, DeskPermissions, LifecycleAction, MatchingStatus,
+    MatchingStatusUpdate, MultisigConfig, PartnershipTerms, PauseScope, PaymentConfirmation,
+    PaymentStatus, PaymentStatusUpdate, PaymentWindow, Role, TradeDetails, TraderPermissions,
+};
 
 pub async fn new(contract: &Contract, sender: &Account) -> anyhow::Result<ExecutionResult<Value>> {
     let (res, _): (ExecutionResult<Value>, Vec<ContractEvent>) =
@@ -53,6 +58,22 @@ pub async fn remove_bank(
     Ok((res, events))
 }
 
+pub async fn suspend_bank(
+    contract: &Contract,
+    bank_id: &str,
+    suspended: bool,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "suspend_bank",
+        contract
+            .call("suspend_bank")
+            .args_json((bank_id, suspended))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
 pub async fn create_bank(
     contract: &Contract,
     bank: &str,
@@ -62,7 +83,43 @@ pub async fn create_bank(
         "create_bank",
         contract
             .call("create_bank")
-            .args_json((bank,))
+            .args_json((bank, Option::<AccountId>::None, Option::<OperatorKey>::None))
+            .deposit(storage_cost)
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn create_bank_from(
+    contract: &Contract,
+    sender: &Account,
+    bank: &str,
+    storage_cost: NearToken,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "create_bank",
+        sender
+            .call(contract.id(), "create_bank")
+            .args_json((bank, Option::<AccountId>::None, Option::<OperatorKey>::None))
+            .deposit(storage_cost)
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn create_bank_with_operator_key(
+    contract: &Contract,
+    bank: &str,
+    storage_cost: NearToken,
+    operator_key: &OperatorKey,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "create_bank",
+        contract
+            .call("create_bank")
+            .args_json((bank, Option::<AccountId>::None, Some(operator_key)))
             .deposit(storage_cost)
             .max_gas(),
     )
@@ -70,6 +127,226 @@ pub async fn create_bank(
     Ok((res, events))
 }
 
+pub async fn storage_deposit(
+    contract: &Contract,
+    bank_id: &str,
+    deposit: NearToken,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "storage_deposit",
+        contract
+            .call("storage_deposit")
+            .args_json((bank_id,))
+            .deposit(deposit)
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn storage_withdraw(
+    contract: &Contract,
+    bank_id: &str,
+    amount: Option<U128>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "storage_withdraw",
+        contract
+            .call("storage_withdraw")
+            .args_json((bank_id, amount))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_counterparty_allowed(
+    contract: &Contract,
+    bank_id: &str,
+    counterparty: &str,
+    allowed: bool,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_counterparty_allowed",
+        contract
+            .call("set_counterparty_allowed")
+            .args_json((bank_id, counterparty, allowed))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_approval_threshold(
+    contract: &Contract,
+    bank_id: &str,
+    ccy: &str,
+    threshold: Option<f64>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_approval_threshold",
+        contract
+            .call("set_approval_threshold")
+            .args_json((bank_id, ccy, threshold))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_approver(
+    contract: &Contract,
+    bank_id: &str,
+    account_id: &AccountId,
+    authorised: bool,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_approver",
+        contract
+            .call("set_approver")
+            .args_json((bank_id, account_id, authorised))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn approve_trade(
+    sender: &Account,
+    bank_account_id: &AccountId,
+    trade_id: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "approve_trade",
+        sender
+            .call(bank_account_id, "approve_trade")
+            .args_json((trade_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn reject_trade(
+    sender: &Account,
+    bank_account_id: &AccountId,
+    trade_id: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "reject_trade",
+        sender
+            .call(bank_account_id, "reject_trade")
+            .args_json((trade_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_trader_permissions(
+    contract: &Contract,
+    bank_id: &str,
+    trader_id: &str,
+    permissions: Option<&TraderPermissions>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_trader_permissions",
+        contract
+            .call("set_trader_permissions")
+            .args_json((bank_id, trader_id, permissions))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_desk_permissions(
+    contract: &Contract,
+    bank_id: &str,
+    desk: &str,
+    permissions: Option<&DeskPermissions>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_desk_permissions",
+        contract
+            .call("set_desk_permissions")
+            .args_json((bank_id, desk, permissions))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_permissions_enforced(
+    contract: &Contract,
+    bank_id: &str,
+    enforced: bool,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_permissions_enforced",
+        contract
+            .call("set_permissions_enforced")
+            .args_json((bank_id, enforced))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn propose_partnership(
+    contract: &Contract,
+    bank_id: &str,
+    counterparty: &str,
+    terms: &PartnershipTerms,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let bank_account_id: AccountId = format!("{bank_id}.{}", contract.id()).parse()?;
+    let (res, events) = log_tx_result(
+        "propose_partnership",
+        contract
+            .as_account()
+            .call(&bank_account_id, "propose_partnership")
+            .args_json((counterparty, terms))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn accept_partnership(
+    contract: &Contract,
+    bank_id: &str,
+    counterparty: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let bank_account_id: AccountId = format!("{bank_id}.{}", contract.id()).parse()?;
+    let (res, events) = log_tx_result(
+        "accept_partnership",
+        contract
+            .as_account()
+            .call(&bank_account_id, "accept_partnership")
+            .args_json((counterparty,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_exposure_limit(
+    contract: &Contract,
+    partnership_id: &str,
+    ccy: &str,
+    limit: Option<f64>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_exposure_limit",
+        contract
+            .call("set_exposure_limit")
+            .args_json((partnership_id, ccy, limit))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
 pub async fn perform_trade(
     contract: &Contract,
     bank_id: &str,
@@ -86,6 +363,22 @@ pub async fn perform_trade(
     Ok((res, events))
 }
 
+pub async fn perform_trades(
+    contract: &Contract,
+    bank_id: &str,
+    trades: &[TradeDetails],
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "perform_trades",
+        contract
+            .call("perform_trades")
+            .args_json((bank_id, trades))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
 pub async fn set_matching_status(
     contract: &Contract,
     partnership_id: &str,
@@ -111,6 +404,36 @@ pub async fn set_matching_status(
     Ok((res, events))
 }
 
+pub async fn set_matching_statuses(
+    contract: &Contract,
+    updates: &[MatchingStatusUpdate],
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_matching_statuses",
+        contract
+            .call("set_matching_statuses")
+            .args_json((updates,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_payment_statuses(
+    contract: &Contract,
+    updates: &[PaymentStatusUpdate],
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_payment_statuses",
+        contract
+            .call("set_payment_statuses")
+            .args_json((updates,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
 pub async fn confirm_payment(
     contract: &Contract,
     creditor_id: &str,
@@ -127,3 +450,512 @@ pub async fn confirm_payment(
     .await?;
     Ok((res, events))
 }
+
+pub async fn set_payment_status(
+    contract: &Contract,
+    partnership_id: &str,
+    bank_a_id: &str,
+    bank_b_id: &str,
+    trade_id: &str,
+    payment_status: &PaymentStatus,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_payment_status",
+        contract
+            .call("set_payment_status")
+            .args_json((
+                partnership_id,
+                bank_a_id,
+                bank_b_id,
+                trade_id,
+                payment_status,
+            ))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn run_netting(
+    contract: &Contract,
+    partnership_id: &str,
+    value_date: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "run_netting",
+        contract
+            .call("run_netting")
+            .args_json((partnership_id, value_date))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn confirm_net_payment(
+    contract: &Contract,
+    bank_id: &str,
+    cycle_id: u64,
+    instruction_id: u32,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let bank_account_id: AccountId = format!("{bank_id}.{}", contract.id()).parse()?;
+    let (res, events) = log_tx_result(
+        "confirm_net_payment",
+        contract
+            .as_account()
+            .call(&bank_account_id, "confirm_net_payment")
+            .args_json((cycle_id, instruction_id))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn retry_netting_cycle(
+    contract: &Contract,
+    cycle_id: u64,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "retry_netting_cycle",
+        contract
+            .call("retry_netting_cycle")
+            .args_json((cycle_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn run_multilateral_netting(
+    contract: &Contract,
+    value_date: Option<&str>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "run_multilateral_netting",
+        contract
+            .call("run_multilateral_netting")
+            .args_json((value_date,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_currency_token(
+    contract: &Contract,
+    ccy: &str,
+    token: Option<&CurrencyToken>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_currency_token",
+        contract
+            .call("set_currency_token")
+            .args_json((ccy, token))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_settlement_account(
+    contract: &Contract,
+    bank_id: &str,
+    account_id: Option<&AccountId>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_settlement_account",
+        contract
+            .call("set_settlement_account")
+            .args_json((bank_id, account_id))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn refund_pvp(
+    contract: &Contract,
+    partnership_id: &str,
+    trade_id: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "refund_pvp",
+        contract
+            .call("refund_pvp")
+            .args_json((partnership_id, trade_id))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn ft_withdraw(
+    contract: &Contract,
+    sender: &Account,
+    token_id: &AccountId,
+    amount: Option<U128>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "ft_withdraw",
+        sender
+            .call(contract.id(), "ft_withdraw")
+            .args_json((token_id, amount))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn storage_deposit(
+    token: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "storage_deposit",
+        token
+            .call("storage_deposit")
+            .args_json((account_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn mint(
+    token: &Contract,
+    account_id: &AccountId,
+    amount: U128,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "mint",
+        token.call("mint").args_json((account_id, amount)).max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn ft_transfer_call(
+    token: &Contract,
+    sender: &Account,
+    receiver_id: &AccountId,
+    amount: U128,
+    msg: String,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "ft_transfer_call",
+        sender
+            .call(token.id(), "ft_transfer_call")
+            .args_json((receiver_id, amount, Option::<String>::None, msg))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn queue_settlement(
+    contract: &Contract,
+    partnership_id: &str,
+    trade_id: &str,
+    priority: u8,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "queue_settlement",
+        contract
+            .call("queue_settlement")
+            .args_json((partnership_id, trade_id, priority))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn grant_role(
+    contract: &Contract,
+    role: Role,
+    account_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "grant_role",
+        contract
+            .call("grant_role")
+            .args_json((role, account_id))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn submit_fixing(
+    contract: &Contract,
+    sender: &Account,
+    partnership_id: &str,
+    trade_id: &str,
+    fixing_rate: f64,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "submit_fixing",
+        sender
+            .call(contract.id(), "submit_fixing")
+            .args_json((partnership_id, trade_id, fixing_rate))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn request_lifecycle(
+    contract: &Contract,
+    bank_id: &str,
+    partnership_id: &str,
+    trade_id: &str,
+    action: &LifecycleAction,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let bank_account_id: AccountId = format!("{bank_id}.{}", contract.id()).parse()?;
+    let (res, events) = log_tx_result(
+        "request_lifecycle",
+        contract
+            .as_account()
+            .call(&bank_account_id, "request_lifecycle")
+            .args_json((partnership_id, trade_id, action))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_holidays(
+    contract: &Contract,
+    financial_centre: &str,
+    holidays: &[&str],
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_holidays",
+        contract
+            .call("set_holidays")
+            .args_json((financial_centre, holidays))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_payment_window(
+    contract: &Contract,
+    ccy: &str,
+    window: Option<&PaymentWindow>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_payment_window",
+        contract
+            .call("set_payment_window")
+            .args_json((ccy, window))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn check_cut_offs(
+    contract: &Contract,
+    skip: Option<u32>,
+    limit: Option<u32>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "check_cut_offs",
+        contract
+            .call("check_cut_offs")
+            .args_json((skip, limit))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn add_screened_counterparty(
+    contract: &Contract,
+    sender: &Account,
+    counterparty_id: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "add_screened_counterparty",
+        sender
+            .call(contract.id(), "add_screened_counterparty")
+            .args_json((counterparty_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn pause(
+    contract: &Contract,
+    sender: &Account,
+    scope: &PauseScope,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "pause",
+        sender
+            .call(contract.id(), "pause")
+            .args_json((scope,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn unpause(
+    contract: &Contract,
+    sender: &Account,
+    scope: &PauseScope,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "unpause",
+        sender
+            .call(contract.id(), "unpause")
+            .args_json((scope,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn propose_owner(
+    contract: &Contract,
+    sender: &Account,
+    new_owner: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "propose_owner",
+        sender
+            .call(contract.id(), "propose_owner")
+            .args_json((new_owner,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn accept_ownership(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "accept_ownership",
+        sender.call(contract.id(), "accept_ownership").max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn set_multisig(
+    contract: &Contract,
+    sender: &Account,
+    config: Option<&MultisigConfig>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "set_multisig",
+        sender
+            .call(contract.id(), "set_multisig")
+            .args_json((config,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn propose_operation(
+    contract: &Contract,
+    sender: &Account,
+    operation: &AdminOperation,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "propose_operation",
+        sender
+            .call(contract.id(), "propose_operation")
+            .args_json((operation,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn approve_operation(
+    contract: &Contract,
+    sender: &Account,
+    proposal_id: u64,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "approve_operation",
+        sender
+            .call(contract.id(), "approve_operation")
+            .args_json((proposal_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn rotate_operator_key(
+    contract: &Contract,
+    bank_id: &str,
+    operator_key: &OperatorKey,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "rotate_operator_key",
+        contract
+            .call("rotate_operator_key")
+            .args_json((bank_id, operator_key))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+pub async fn revoke_operator_key(
+    contract: &Contract,
+    bank_id: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "revoke_operator_key",
+        contract
+            .call("revoke_operator_key")
+            .args_json((bank_id,))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+/// Submit a trade directly to the bank contract, signed by its operator.
+pub async fn operator_perform_trade(
+    operator: &Account,
+    trade_details: &TradeDetails,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "perform_trade",
+        operator
+            .call(operator.id(), "perform_trade")
+            .args_json((trade_details, Option::<bool>::None))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
+
+/// Confirm a payment directly with the bank contract, signed by its operator.
+pub async fn operator_confirm_payment(
+    operator: &Account,
+    trade_id: &str,
+    confirmation: &PaymentConfirmation,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "confirm_payment",
+        operator
+            .call(operator.id(), "confirm_payment")
+            .args_json((trade_id, confirmation))
+            .max_gas(),
+    )
+    .await?;
+    Ok((res, events))
+}
@@ -2,16 +2,19 @@ pub mod call;
 pub mod view;
 
 use futures::Future;
+use near_sdk::json_types::U128;
 use near_workspaces::{
     network::{NetworkClient, Sandbox},
     operations::CallTransaction,
     result::{ExecutionFinalResult, ExecutionResult, Value, ViewResultDetails},
-    types::{KeyType, SecretKey},
+    types::{KeyType, NearToken, SecretKey},
     AccountId, Contract, DevNetwork, Worker,
 };
 use owo_colors::OwoColorize;
 use rtp_common::{ContractEvent, KNOWN_EVENT_KINDS};
-use rtp_contract_common::{MatchingStatus, PaymentStatus, RtpEvent};
+use rtp_contract_common::{
+    CurrencyToken, MatchingStatus, PartnershipTerms, PaymentStatus, Product, Role, RtpEvent,
+};
 use serde::Serialize;
 use std::{
     fmt, thread,
@@ -47,10 +50,143 @@ pub async fn initialize_contracts() -> anyhow::Result<(Worker<Sandbox>, Contract
         .into_result()?;
 
     call::new(&contract, contract.as_account()).await?;
+    call::grant_role(&contract, Role::Matcher, contract.id()).await?;
 
     Ok((worker, contract))
 }
 
+pub fn default_partnership_terms() -> PartnershipTerms {
+    PartnershipTerms {
+        products: vec![Product::Spot, Product::Ndf, Product::Fwd, Product::Swap],
+        currencies: vec!["EUR".to_string(), "USD".to_string()],
+        settlement_methods: vec!["Nostro".to_string()],
+        matching_rule_set: "default".to_string(),
+    }
+}
+
+/// Deploys a test token for tokenized cash of given currency and registers it in the factory.
+/// All accounts get registered and funded with `initial_balance`.
+pub async fn initialize_token(
+    worker: &Worker<Sandbox>,
+    factory: &Contract,
+    ccy: &str,
+    accounts: &[&AccountId],
+    initial_balance: u128,
+) -> anyhow::Result<Contract> {
+    let wasm = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../res/test_token.wasm"
+    ))?;
+    let token = worker.dev_deploy(&wasm).await?;
+    call::new(&token, token.as_account()).await?;
+
+    call::storage_deposit(&token, factory.id()).await?;
+    for account_id in accounts {
+        call::storage_deposit(&token, account_id).await?;
+        call::mint(&token, account_id, U128(initial_balance)).await?;
+    }
+    call::set_currency_token(
+        factory,
+        ccy,
+        Some(&CurrencyToken {
+            token_id: token.id().clone(),
+            decimals: 2,
+        }),
+    )
+    .await?;
+
+    Ok(token)
+}
+
+/// NDFs can only be paid after their fixing, which is submitted by the factory as rate provider.
+pub async fn fix_ndf(
+    factory: &Contract,
+    bank_a: &str,
+    bank_b: &str,
+    trade_id: &str,
+    product: &Product,
+) -> anyhow::Result<()> {
+    if *product != Product::Ndf {
+        return Ok(());
+    }
+    let partnership_id = view::get_partnership_id(factory, bank_a, bank_b).await?;
+    call::grant_role(factory, Role::RateProvider, factory.id()).await?;
+    call::submit_fixing(
+        factory,
+        factory.as_account(),
+        &partnership_id,
+        trade_id,
+        1.1,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Two banks, which most tests trade between.
+pub struct TestBanks {
+    pub bank_a: String,
+    pub bank_b: String,
+    pub bank_a_id: String,
+    pub bank_b_id: String,
+    pub partnership_id: String,
+}
+
+/// Stores the bank contract binary and creates the bank contracts of both test banks.
+pub async fn create_banks(factory: &Contract) -> anyhow::Result<TestBanks> {
+    let bank_a = "Deutsche Bank".to_string();
+    let bank_b = "Sparkasse".to_string();
+
+    let wasm = include_bytes!("../../../../res/rtp.wasm");
+    call::store_contract(factory, factory.as_account(), wasm.to_vec()).await?;
+
+    let storage_cost = view::get_bank_storage_cost(factory).await?;
+    call::create_bank(factory, &bank_a, NearToken::from_yoctonear(storage_cost)).await?;
+    call::create_bank(factory, &bank_b, NearToken::from_yoctonear(storage_cost)).await?;
+    let bank_a_id = view::get_bank_id(factory, &bank_a).await?;
+    let bank_b_id = view::get_bank_id(factory, &bank_b).await?;
+    let partnership_id = view::get_partnership_id(factory, &bank_a, &bank_b).await?;
+
+    Ok(TestBanks {
+        bank_a,
+        bank_b,
+        bank_a_id,
+        bank_b_id,
+        partnership_id,
+    })
+}
+
+/// Creates both test banks and establishes an active partnership between them with default terms.
+pub async fn setup_banks(factory: &Contract) -> anyhow::Result<TestBanks> {
+    let banks = create_banks(factory).await?;
+    setup_partnership(
+        factory,
+        &banks.bank_a_id,
+        &banks.bank_a,
+        &banks.bank_b_id,
+        &banks.bank_b,
+    )
+    .await?;
+
+    Ok(banks)
+}
+
+/// Establishes an active partnership between both banks with default terms.
+pub async fn setup_partnership(
+    contract: &Contract,
+    bank_a_id: &str,
+    bank_a: &str,
+    bank_b_id: &str,
+    bank_b: &str,
+) -> anyhow::Result<()> {
+    call::propose_partnership(contract, bank_a_id, bank_b, &default_partnership_terms()).await?;
+    call::accept_partnership(contract, bank_b_id, bank_a).await?;
+    call::set_counterparty_allowed(contract, bank_a_id, bank_b, true).await?;
+    call::set_counterparty_allowed(contract, bank_b_id, bank_a, true).await?;
+    call::storage_deposit(contract, bank_a_id, NearToken::from_near(1)).await?;
+    call::storage_deposit(contract, bank_b_id, NearToken::from_near(1)).await?;
+    Ok(())
+}
+
 pub async fn log_tx_result(
     ident: &str,
     tx: CallTransaction,
@@ -77,7 +213,7 @@ pub async fn log_tx_result(
     for outcome in res.receipt_outcomes() {
         if !outcome.logs.is_empty() {
             for log in outcome.logs.iter() {
-                if log.starts_with("EVENT_JSON:") {
+                if log.starts_with("EVENT_JSON:") && log.contains(r#""standard":"rtp""#) {
                     let event: ContractEvent =
                         serde_json::from_str(&log.replace("EVENT_JSON:", ""))?;
                     events.push(event.clone());
@@ -137,6 +273,11 @@ where
             .unwrap();
         KNOWN_EVENT_KINDS.contains(&event_str)
     });
+    actual
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .for_each(remove_block_time);
     let mut expected = vec![];
     for event in events {
         let mut expected_event = serde_json::to_value(event)?;
@@ -147,6 +288,7 @@ where
         }
         ev.insert("standard".into(), "rtp".into());
         ev.insert("version".into(), "1.0.0".into());
+        remove_block_time(&mut expected_event);
         expected.push(expected_event);
     }
     assert_eq!(
@@ -159,6 +301,13 @@ where
     Ok(())
 }
 
+/// Block times are set by the chain and cannot be known upfront.
+fn remove_block_time(event: &mut serde_json::Value) {
+    if let Some(data) = event.get_mut("data").and_then(|data| data.as_object_mut()) {
+        data.remove("block_time");
+    }
+}
+
 pub async fn assert_trade_matching_status<T: ?Sized + NetworkClient>(
     worker: &Worker<T>,
     contract_id: &AccountId,
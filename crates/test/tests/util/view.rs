@@ -1,7 +1,11 @@
 use super::log_view_result;
-use near_sdk::Balance;
+use near_contract_standards::storage_management::StorageBalance;
+use near_sdk::{json_types::U128, Balance};
 use near_workspaces::{network::NetworkClient, AccountId, Contract, Worker};
-use rtp_contract_common::Trade;
+use rtp_contract_common::{
+    AdminProposal, AuditEntry, Exposure, NetPosition, NettingCycle, Partnership, PauseScope,
+    PvpEscrow, QueuedSettlement, Role, Settlement, SettlementInstruction, Trade, TradeLatency,
+};
 
 pub async fn get_bank_storage_cost(contract: &Contract) -> anyhow::Result<Balance> {
     let res = log_view_result(
@@ -58,6 +62,170 @@ pub async fn get_partnership_id(
     Ok(res.json()?)
 }
 
+pub async fn get_partnership(
+    contract: &Contract,
+    partnership_id: &str,
+) -> anyhow::Result<Option<Partnership>> {
+    let res = log_view_result(
+        contract
+            .call("get_partnership")
+            .args_json((partnership_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_exposure(
+    contract: &Contract,
+    partnership_id: &str,
+    ccy: &str,
+) -> anyhow::Result<Option<Exposure>> {
+    let res = log_view_result(
+        contract
+            .call("get_exposure")
+            .args_json((partnership_id, ccy))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_net_obligations(
+    contract: &Contract,
+    partnership_id: &str,
+    value_date: &str,
+) -> anyhow::Result<Vec<SettlementInstruction>> {
+    let res = log_view_result(
+        contract
+            .call("get_net_obligations")
+            .args_json((partnership_id, value_date))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_net_positions(
+    contract: &Contract,
+    value_date: Option<&str>,
+) -> anyhow::Result<Vec<NetPosition>> {
+    let res = log_view_result(
+        contract
+            .call("get_net_positions")
+            .args_json((value_date,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_multilateral_net_obligations(
+    contract: &Contract,
+    value_date: Option<&str>,
+) -> anyhow::Result<Vec<SettlementInstruction>> {
+    let res = log_view_result(
+        contract
+            .call("get_multilateral_net_obligations")
+            .args_json((value_date,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_netting_cycle(
+    contract: &Contract,
+    cycle_id: u64,
+) -> anyhow::Result<Option<NettingCycle>> {
+    let res = log_view_result(
+        contract
+            .call("get_netting_cycle")
+            .args_json((cycle_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_pvp_escrow(
+    contract: &Contract,
+    partnership_id: &str,
+    trade_id: &str,
+) -> anyhow::Result<Option<PvpEscrow>> {
+    let res = log_view_result(
+        contract
+            .call("get_pvp_escrow")
+            .args_json((partnership_id, trade_id))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_token_balance(
+    contract: &Contract,
+    account_id: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<U128> {
+    let res = log_view_result(
+        contract
+            .call("get_token_balance")
+            .args_json((account_id, token_id))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_liquidity(contract: &Contract, bank_id: &str, ccy: &str) -> anyhow::Result<U128> {
+    let res = log_view_result(
+        contract
+            .call("get_liquidity")
+            .args_json((bank_id, ccy))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_settlement_queue(
+    contract: &Contract,
+    bank_id: &str,
+    ccy: &str,
+) -> anyhow::Result<Vec<((String, String), QueuedSettlement)>> {
+    let res = log_view_result(
+        contract
+            .call("get_settlement_queue")
+            .args_json((bank_id, ccy))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_balance_of(token: &Contract, account_id: &AccountId) -> anyhow::Result<U128> {
+    let res = log_view_result(
+        token
+            .call("ft_balance_of")
+            .args_json((account_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
 pub async fn get_trade<T: ?Sized + NetworkClient>(
     worker: &Worker<T>,
     contract_id: &AccountId,
@@ -71,3 +239,150 @@ pub async fn get_trade<T: ?Sized + NetworkClient>(
     )?;
     Ok(res.json()?)
 }
+
+pub async fn get_trade_latency<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+    trade_id: &str,
+) -> anyhow::Result<TradeLatency> {
+    let res = log_view_result(
+        worker
+            .view(contract_id, "get_trade_latency")
+            .args_json((trade_id,))
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_trade_history<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+    trade_id: &str,
+) -> anyhow::Result<Vec<AuditEntry>> {
+    let res = log_view_result(
+        worker
+            .view(contract_id, "get_trade_history")
+            .args_json((trade_id,))
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn is_swap_complete<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+    trade_id: &str,
+) -> anyhow::Result<bool> {
+    let res = log_view_result(
+        worker
+            .view(contract_id, "is_swap_complete")
+            .args_json((trade_id,))
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_value_date(
+    contract: &Contract,
+    payment_calendar: &str,
+    trade_date: &str,
+    settlement: &Settlement,
+) -> anyhow::Result<String> {
+    let res = log_view_result(
+        contract
+            .call("get_value_date")
+            .args_json((payment_calendar, trade_date, settlement))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn storage_balance_of<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+) -> anyhow::Result<StorageBalance> {
+    let res = log_view_result(worker.view(contract_id, "storage_balance_of").await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_storage_top_up<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+) -> anyhow::Result<U128> {
+    let res = log_view_result(worker.view(contract_id, "get_storage_top_up").await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_allowed_counterparties<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+) -> anyhow::Result<Vec<String>> {
+    let res = log_view_result(
+        worker
+            .view(contract_id, "get_allowed_counterparties")
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_paused_scopes(contract: &Contract) -> anyhow::Result<Vec<PauseScope>> {
+    let res = log_view_result(contract.call("get_paused_scopes").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn is_bank_paused<T: ?Sized + NetworkClient>(
+    worker: &Worker<T>,
+    contract_id: &AccountId,
+) -> anyhow::Result<bool> {
+    let res = log_view_result(worker.view(contract_id, "is_paused").await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_owner(contract: &Contract) -> anyhow::Result<AccountId> {
+    let res = log_view_result(contract.call("get_owner").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn has_role(
+    contract: &Contract,
+    role: Role,
+    account_id: &AccountId,
+) -> anyhow::Result<bool> {
+    let res = log_view_result(
+        contract
+            .call("has_role")
+            .args_json((role, account_id))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_admin_proposal(
+    contract: &Contract,
+    proposal_id: u64,
+) -> anyhow::Result<Option<AdminProposal>> {
+    let res = log_view_result(
+        contract
+            .call("get_admin_proposal")
+            .args_json((proposal_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_max_trade_batch_size(contract: &Contract) -> anyhow::Result<u32> {
+    let res = log_view_result(
+        contract
+            .call("get_max_trade_batch_size")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}